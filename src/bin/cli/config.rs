@@ -0,0 +1,136 @@
+//! `flc config` subcommands: create, validate and inspect configuration files.
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use fast_license_checker::config::{
+    config_schema, create_config_template, load_effective_config, EffectiveConfig,
+};
+use fast_license_checker::error::ExitStatus;
+
+use super::output::OutputFormat;
+use crate::Cli;
+
+#[derive(clap::Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write a starter configuration file (.flc.toml or .flc.json in PATH)
+    Init {
+        /// Configuration file format
+        #[arg(long, value_enum, default_value = "toml")]
+        format: ConfigFormat,
+
+        /// Overwrite an existing configuration file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Load the configuration and report invalid fields
+    Validate,
+    /// Print the effective configuration and where each value comes from
+    Show,
+    /// Print a JSON Schema for configuration files, for editor completion
+    Schema,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ConfigFormat {
+    /// TOML (.flc.toml)
+    Toml,
+    /// JSON (.flc.json)
+    Json,
+}
+
+pub fn run(cli: &Cli, command: &ConfigCommand) -> Result<ExitStatus> {
+    match command {
+        ConfigCommand::Init { format, force } => init(cli, *format, *force),
+        ConfigCommand::Validate => validate(cli),
+        ConfigCommand::Show => show(cli),
+        ConfigCommand::Schema => {
+            let schema = serde_json::to_string_pretty(&config_schema())?;
+            write_stdout(&format!("{schema}\n"));
+            Ok(ExitStatus::Success)
+        }
+    }
+}
+
+fn init(cli: &Cli, format: ConfigFormat, force: bool) -> Result<ExitStatus> {
+    let (name, format) = match format {
+        ConfigFormat::Toml => (".flc.toml", "toml"),
+        ConfigFormat::Json => (".flc.json", "json"),
+    };
+    let path = cli.config.clone().unwrap_or_else(|| cli.path.join(name));
+
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists (use --force to overwrite)", path.display());
+    }
+    create_config_template(&path, format)?;
+
+    writeln!(std::io::stderr(), "Created {}", path.display())?;
+    Ok(ExitStatus::Success)
+}
+
+fn validate(cli: &Cli) -> Result<ExitStatus> {
+    let effective = load(cli)?;
+    let mut stderr = std::io::stderr();
+
+    match &effective.file {
+        Some(path) => writeln!(stderr, "{}: valid", path.display())?,
+        None => writeln!(stderr, "No configuration file found; using defaults")?,
+    }
+    if effective.config.license_header.is_empty() {
+        writeln!(stderr, "warning: license_header is not set")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+fn show(cli: &Cli) -> Result<ExitStatus> {
+    let effective = load(cli)?;
+    let table = toml::Table::try_from(&effective.config)
+        .context("Failed to serialize the effective configuration")?;
+    let source = |key: &str| {
+        effective.sources.get(key).map_or_else(|| "default".to_string(), ToString::to_string)
+    };
+
+    if cli.output == OutputFormat::Json {
+        let sources: serde_json::Map<String, serde_json::Value> =
+            table.keys().map(|key| (key.clone(), source(key).into())).collect();
+        let file = effective.file.as_ref().map(|path| path.display().to_string());
+        let report: serde_json::Map<String, serde_json::Value> = [
+            ("file".to_string(), file.into()),
+            ("config".to_string(), serde_json::to_value(&effective.config)?),
+            ("sources".to_string(), sources.into()),
+        ]
+        .into_iter()
+        .collect();
+        write_stdout(&format!("{}\n", serde_json::to_string_pretty(&report)?));
+        return Ok(ExitStatus::Success);
+    }
+
+    // Plain values first, then one section per table, as TOML requires
+    let mut out = String::new();
+    for (key, value) in table.iter().filter(|(_, value)| !value.is_table()) {
+        writeln!(out, "{key} = {value}  # {}", source(key))?;
+    }
+    for (key, value) in &table {
+        if let toml::Value::Table(entries) = value {
+            writeln!(out, "\n# {}\n[{key}]", source(key))?;
+            for (entry, style) in entries {
+                writeln!(out, "{} = {style}", toml::Value::from(entry.as_str()))?;
+            }
+        }
+    }
+    write_stdout(&out);
+
+    Ok(ExitStatus::Success)
+}
+
+/// Writes to stdout, ignoring errors such as a closed pipe (`flc config schema | head`)
+fn write_stdout(s: &str) {
+    let _ = std::io::stdout().write_all(s.as_bytes());
+}
+
+fn load(cli: &Cli) -> Result<EffectiveConfig> {
+    let overrides = crate::cli_overrides(cli)?;
+    Ok(load_effective_config(&cli.path, cli.config.as_deref(), overrides)?)
+}
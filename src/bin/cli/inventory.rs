@@ -0,0 +1,142 @@
+//! `flc inventory`: report the licenses and copyright holders found in a tree,
+//! and the CycloneDX export of a scan (`--cyclonedx`).
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use fast_license_checker::config::load_config_from;
+use fast_license_checker::error::ExitStatus;
+use fast_license_checker::inventory::{cyclonedx, object, spdx, Inventory, InventoryEntry};
+use fast_license_checker::types::ScanSummary;
+
+use super::output::OutputFormat;
+use crate::Cli;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum InventoryFormat {
+    /// Licenses and holders with file counts
+    Text,
+    /// Licenses, holders and per-file records as JSON
+    Json,
+    /// SPDX 2.3 tag-value document
+    Spdx,
+    /// SPDX 2.3 JSON document
+    SpdxJson,
+}
+
+pub fn run(
+    cli: &Cli,
+    format: Option<InventoryFormat>,
+    output: Option<&Path>,
+) -> Result<ExitStatus> {
+    let config = load_config_from(&cli.path, cli.config.as_deref(), crate::cli_overrides(cli)?)
+        .context("Failed to load configuration")?;
+    let inventory = Inventory::collect(&cli.path, &config)?;
+
+    let format = format.unwrap_or(match cli.output {
+        OutputFormat::Json => InventoryFormat::Json,
+        _ => InventoryFormat::Text,
+    });
+    let rendered = match format {
+        InventoryFormat::Text => text(&inventory)?,
+        InventoryFormat::Json => {
+            format!("{}\n", serde_json::to_string_pretty(&json(&inventory))?)
+        }
+        InventoryFormat::Spdx => spdx::to_tag_value(&inventory, &document_info(cli, &inventory)),
+        InventoryFormat::SpdxJson => {
+            let document = spdx::to_json(&inventory, &document_info(cli, &inventory));
+            format!("{}\n", serde_json::to_string_pretty(&document)?)
+        }
+    };
+
+    match output {
+        Some(path) => std::fs::write(path, rendered)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        // Ignore errors such as a closed pipe (`flc inventory | head`)
+        None => {
+            let _ = std::io::stdout().write_all(rendered.as_bytes());
+        }
+    }
+    Ok(ExitStatus::Success)
+}
+
+/// Writes a CycloneDX bill of materials from the evidence of a scan
+pub fn write_cyclonedx(cli: &Cli, summary: &ScanSummary, path: &Path) -> Result<()> {
+    let bom = cyclonedx::to_json(&document_name(cli), &cli.path, &summary.evidence);
+    std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(&bom)?))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// SPDX document info named after the scanned directory
+fn document_info(cli: &Cli, inventory: &Inventory) -> spdx::DocumentInfo {
+    spdx::DocumentInfo::new(document_name(cli), inventory)
+}
+
+/// The scanned directory's name
+pub fn document_name(cli: &Cli) -> String {
+    let root = std::fs::canonicalize(&cli.path).unwrap_or_else(|_| cli.path.clone());
+    root.file_name()
+        .map_or_else(|| "flc-inventory".to_string(), |name| name.to_string_lossy().into_owned())
+}
+
+fn text(inventory: &Inventory) -> Result<String> {
+    let mut out = String::new();
+    writeln!(out, "{} files under {}", inventory.files.len(), inventory.root.display())?;
+    for (title, entries) in
+        [("Licenses", inventory.licenses()), ("Copyright holders", inventory.holders())]
+    {
+        writeln!(out, "\n{title}:")?;
+        if entries.is_empty() {
+            writeln!(out, "  (none)")?;
+        }
+        for entry in &entries {
+            let examples: Vec<String> =
+                entry.examples.iter().map(|path| path.display().to_string()).collect();
+            writeln!(out, "  {:>6}  {}  (e.g. {})", entry.files, entry.name, examples.join(", "))?;
+        }
+    }
+    Ok(out)
+}
+
+fn json(inventory: &Inventory) -> Value {
+    let entries = |entries: Vec<InventoryEntry>| -> Value {
+        entries
+            .into_iter()
+            .map(|entry| {
+                object([
+                    ("name", entry.name.into()),
+                    ("files", entry.files.into()),
+                    ("examples", paths(&entry.examples)),
+                ])
+            })
+            .collect()
+    };
+    let files: Value = inventory
+        .files
+        .iter()
+        .map(|file| {
+            object([
+                ("path", file.path.display().to_string().into()),
+                ("license", file.license_expression().into()),
+                ("copyrights", file.copyrights.clone().into()),
+                ("attributions", file.attributions.clone().into()),
+                ("third_party", file.third_party.into()),
+                ("sha1", file.sha1.clone().into()),
+            ])
+        })
+        .collect();
+
+    object([
+        ("root", inventory.root.display().to_string().into()),
+        ("licenses", entries(inventory.licenses())),
+        ("holders", entries(inventory.holders())),
+        ("files", files),
+    ])
+}
+
+fn paths(paths: &[PathBuf]) -> Value {
+    paths.iter().map(|path| Value::from(path.display().to_string())).collect()
+}
@@ -0,0 +1,63 @@
+//! `flc notice`: generate the third-party notice from the inventory, or check
+//! that the committed one is up to date (`--check`).
+
+use anyhow::{Context, Result};
+use std::io::Write as _;
+use std::path::Path;
+
+use fast_license_checker::config::load_config_from;
+use fast_license_checker::error::ExitStatus;
+use fast_license_checker::inventory::notice::{self, NoticeFormat};
+use fast_license_checker::inventory::Inventory;
+
+use crate::Cli;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum NoticeFileFormat {
+    /// Plain text, written to NOTICE
+    Text,
+    /// Markdown, written to THIRD_PARTY_LICENSES.md
+    Markdown,
+}
+
+impl From<NoticeFileFormat> for NoticeFormat {
+    fn from(format: NoticeFileFormat) -> Self {
+        match format {
+            NoticeFileFormat::Text => Self::Text,
+            NoticeFileFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
+pub fn run(
+    cli: &Cli,
+    format: NoticeFileFormat,
+    output: Option<&Path>,
+    check: bool,
+) -> Result<ExitStatus> {
+    let config = load_config_from(&cli.path, cli.config.as_deref(), crate::cli_overrides(cli)?)
+        .context("Failed to load configuration")?;
+    let mut inventory = Inventory::collect(&cli.path, &config)?;
+
+    let format = NoticeFormat::from(format);
+    let path = output.map_or_else(|| cli.path.join(format.file_name()), Path::to_path_buf);
+    // The notice quotes copyright lines and must not list itself
+    inventory.files.retain(|file| cli.path.join(&file.path) != path);
+    let rendered = notice::render(&inventory, notice::project_name(&cli.path).as_deref(), format);
+
+    let mut stderr = std::io::stderr();
+    if check {
+        // A missing file is out of date like a stale one
+        if std::fs::read(&path).ok().as_deref() == Some(rendered.as_bytes()) {
+            writeln!(stderr, "{} is up to date", path.display())?;
+            return Ok(ExitStatus::Success);
+        }
+        writeln!(stderr, "{} is out of date; run `flc notice` to regenerate it", path.display())?;
+        return Ok(ExitStatus::Violations);
+    }
+
+    std::fs::write(&path, rendered)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    writeln!(stderr, "Wrote {}", path.display())?;
+    Ok(ExitStatus::Success)
+}
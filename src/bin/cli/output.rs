@@ -1,4 +1,8 @@
-use fast_license_checker::types::ScanSummary;
+use fast_license_checker::types::{
+    CopyrightIssue, CopyrightResult, FileStatus, LicenseClassification, PackageIssue,
+    PackageResult, PolicyVerdict, ScanResult, ScanSummary, SkipReason,
+};
+use std::collections::BTreeMap;
 use std::io::Write;
 
 #[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
@@ -24,16 +28,23 @@ fn write_fmt_stdout(writer: &mut impl Write, args: std::fmt::Arguments) {
     let _ = writer.write_fmt(args);
 }
 
-pub fn print_summary(summary: &ScanSummary, format: OutputFormat, color: bool) {
+/// Prints the summary; `list_suppressions` lists suppressed files in text and
+/// GitHub output (JSON always lists them)
+pub fn print_summary(
+    summary: &ScanSummary,
+    format: OutputFormat,
+    color: bool,
+    list_suppressions: bool,
+) {
     match format {
-        OutputFormat::Text => print_text(summary, color),
+        OutputFormat::Text => print_text(summary, color, list_suppressions),
         OutputFormat::Json => print_json(summary),
-        OutputFormat::Github => print_github(summary),
+        OutputFormat::Github => print_github(summary, list_suppressions),
     }
 }
 
 #[allow(clippy::arithmetic_side_effects)] // Intentional arithmetic for progress bar calculation
-fn print_text(summary: &ScanSummary, color: bool) {
+fn print_text(summary: &ScanSummary, color: bool, list_suppressions: bool) {
     let mut stdout = std::io::stdout().lock();
 
     if summary.total == 0 {
@@ -164,8 +175,298 @@ fn print_text(summary: &ScanSummary, color: bool) {
                 &mut stdout,
                 format_args!("  {} files skipped (binary, unsupported, etc.)\n", summary.skipped),
             );
+            if summary.too_large > 0 {
+                write_fmt_stdout(
+                    &mut stdout,
+                    format_args!("  {} files larger than max_file_size\n", summary.too_large),
+                );
+            }
+            if !summary.suppressed.is_empty() && !list_suppressions {
+                write_fmt_stdout(
+                    &mut stdout,
+                    format_args!(
+                        "  {} files suppressed by markers (--list-suppressions to show)\n",
+                        summary.suppressed.len()
+                    ),
+                );
+            }
+        }
+
+        // Show suppressed files with their justification on request
+        if !summary.suppressed.is_empty() && list_suppressions {
+            if color {
+                write_stdout(&mut stdout, "\x1b[36m"); // Cyan
+            }
+            write_stdout(&mut stdout, "Suppressed files:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.suppressed {
+                if let Some(reason) = result.status.skip_reason() {
+                    write_fmt_stdout(&mut stdout, format_args!("  {}: {}\n", result.path, reason));
+                }
+            }
+        }
+
+        // Show the license policy per license, then the files that need action
+        if !summary.policy.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[1m"); // Bold
+            }
+            write_stdout(&mut stdout, "License policy:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for ((license, verdict), count) in policy_by_license(summary) {
+                write_fmt_stdout(
+                    &mut stdout,
+                    format_args!("  {}: {} {}\n", license, count, verdict),
+                );
+            }
+            for decision in summary.policy.iter().filter(|d| d.verdict != PolicyVerdict::Allowed) {
+                write_fmt_stdout(&mut stdout, format_args!("  {}\n", decision));
+            }
+        }
+
+        // Show copyright holders that are not allowed or not spelled canonically
+        if !summary.copyright.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[33m"); // Yellow
+            }
+            write_stdout(&mut stdout, "Copyright holders:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.copyright {
+                write_fmt_stdout(&mut stdout, format_args!("  {}\n", result));
+            }
+        }
+
+        // Show packages whose license file is missing or does not match their headers
+        if !summary.packages.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[31m"); // Red
+            }
+            write_stdout(&mut stdout, "Package license files:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.packages {
+                write_fmt_stdout(&mut stdout, format_args!("  {}\n", result));
+            }
+        }
+
+        // Show files carrying a different license, which fix mode leaves alone
+        if !summary.foreign_licenses.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[31m"); // Red
+            }
+            write_stdout(&mut stdout, "Foreign licenses (fix with --fix --force):\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.foreign_licenses {
+                write_fmt_stdout(&mut stdout, format_args!("  {}\n", result));
+            }
+        }
+
+        // Show third-party files with the license found in each
+        if !summary.third_party.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[34m"); // Blue
+            }
+            write_stdout(&mut stdout, "Third-party files:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.third_party {
+                write_fmt_stdout(&mut stdout, format_args!("  {}\n", result));
+            }
+        }
+
+        // Show unreadable files individually, with their OS error
+        if !summary.unreadable.is_empty() {
+            if color {
+                write_stdout(&mut stdout, "\x1b[35m"); // Magenta
+            }
+            write_stdout(&mut stdout, "Unreadable files:\n");
+            if color {
+                write_stdout(&mut stdout, "\x1b[0m");
+            }
+
+            for result in &summary.unreadable {
+                if let Some(reason) = result.status.access_error() {
+                    write_fmt_stdout(&mut stdout, format_args!("  {}: {}\n", result.path, reason));
+                }
+            }
+        }
+    }
+}
+
+/// Stable identifier for an access error kind in machine-readable output
+fn access_error_kind(reason: &SkipReason) -> &'static str {
+    match reason {
+        SkipReason::PermissionDenied { .. } => "permission_denied",
+        SkipReason::BrokenSymlink { .. } => "broken_symlink",
+        _ => "io_error",
+    }
+}
+
+fn unreadable_json(result: &ScanResult) -> serde_json::Value {
+    let mut obj = serde_json::Map::new();
+    obj.insert("path".to_string(), serde_json::Value::String(result.path.to_string()));
+    let status = if result.status.is_unreadable() { "failed" } else { "skipped" };
+    obj.insert("status".to_string(), serde_json::Value::String(status.to_string()));
+    if let Some(reason) = result.status.access_error() {
+        obj.insert("reason".to_string(), access_error_kind(reason).into());
+        if let Some((_, error)) = reason.access_error() {
+            obj.insert("error".to_string(), serde_json::Value::String(error.to_string()));
         }
     }
+    serde_json::Value::Object(obj)
+}
+
+fn suppressed_json(result: &ScanResult) -> serde_json::Value {
+    let mut obj = serde_json::Map::new();
+    obj.insert("path".to_string(), serde_json::Value::String(result.path.to_string()));
+    if let Some(SkipReason::Suppressed { marker, justification }) = result.status.skip_reason() {
+        obj.insert("marker".to_string(), serde_json::Value::String(marker.clone()));
+        obj.insert(
+            "justification".to_string(),
+            justification.clone().map_or(serde_json::Value::Null, serde_json::Value::String),
+        );
+    }
+    serde_json::Value::Object(obj)
+}
+
+fn third_party_json(result: &ScanResult) -> serde_json::Value {
+    let mut obj = serde_json::Map::new();
+    obj.insert("path".to_string(), serde_json::Value::String(result.path.to_string()));
+    if let FileStatus::ThirdParty { license, allowed } = &result.status {
+        let (kind, id) = match license {
+            LicenseClassification::Ours => ("ours", None),
+            LicenseClassification::Known { id } => ("known", Some(id.clone())),
+            LicenseClassification::Unknown => ("unknown", None),
+            LicenseClassification::None => ("none", None),
+        };
+        obj.insert("license".to_string(), kind.into());
+        obj.insert("id".to_string(), id.map_or(serde_json::Value::Null, serde_json::Value::String));
+        obj.insert("allowed".to_string(), serde_json::Value::Bool(*allowed));
+    }
+    serde_json::Value::Object(obj)
+}
+
+/// Number of files per license and verdict, sorted by license
+fn policy_by_license(summary: &ScanSummary) -> BTreeMap<(&str, PolicyVerdict), usize> {
+    let mut counts = BTreeMap::new();
+    for decision in &summary.policy {
+        let count = counts.entry((decision.license.as_str(), decision.verdict)).or_insert(0usize);
+        *count = count.saturating_add(1);
+    }
+    counts
+}
+
+fn policy_json(summary: &ScanSummary) -> serde_json::Value {
+    let mut licenses = serde_json::Map::new();
+    for ((license, verdict), count) in policy_by_license(summary) {
+        let entry = licenses
+            .entry(license.to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let Some(counts) = entry.as_object_mut() {
+            counts.insert(verdict.to_string(), serde_json::Value::Number(count.into()));
+        }
+    }
+
+    let files = summary
+        .policy
+        .iter()
+        .map(|decision| {
+            let mut obj = serde_json::Map::new();
+            obj.insert("path".to_string(), serde_json::Value::String(decision.path.to_string()));
+            obj.insert("license".to_string(), serde_json::Value::String(decision.license.clone()));
+            obj.insert(
+                "verdict".to_string(),
+                serde_json::Value::String(decision.verdict.to_string()),
+            );
+            serde_json::Value::Object(obj)
+        })
+        .collect();
+
+    let mut obj = serde_json::Map::new();
+    obj.insert("licenses".to_string(), serde_json::Value::Object(licenses));
+    obj.insert("files".to_string(), serde_json::Value::Array(files));
+    serde_json::Value::Object(obj)
+}
+
+fn copyright_json(result: &CopyrightResult) -> serde_json::Value {
+    let (issue, detail) = match &result.issue {
+        CopyrightIssue::UnknownHolder => ("unknown_holder", None),
+        CopyrightIssue::IndividualHolder => ("individual_holder", None),
+        CopyrightIssue::NonCanonical { canonical } => {
+            ("non_canonical", Some(("canonical", serde_json::Value::String(canonical.clone()))))
+        }
+        CopyrightIssue::OutdatedYears { expected } => {
+            ("outdated_years", Some(("expected", serde_json::Value::String(expected.clone()))))
+        }
+        CopyrightIssue::UncoveredAuthors { authors } => (
+            "uncovered_authors",
+            Some((
+                "authors",
+                serde_json::Value::Array(
+                    authors.iter().cloned().map(serde_json::Value::String).collect(),
+                ),
+            )),
+        ),
+    };
+    let mut obj = serde_json::Map::new();
+    obj.insert("path".to_string(), serde_json::Value::String(result.path.to_string()));
+    obj.insert("holder".to_string(), serde_json::Value::String(result.holder.clone()));
+    obj.insert("issue".to_string(), serde_json::Value::String(issue.to_string()));
+    if let Some((key, value)) = detail {
+        obj.insert(key.to_string(), value);
+    }
+    serde_json::Value::Object(obj)
+}
+
+fn package_json(result: &PackageResult) -> serde_json::Value {
+    let issue = match &result.issue {
+        PackageIssue::MissingLicenseFile => "missing_license_file",
+        PackageIssue::UnrecognisedLicenseFile => "unrecognised_license_file",
+        PackageIssue::HeaderMismatch { .. } => "header_mismatch",
+    };
+    let mut obj = serde_json::Map::new();
+    obj.insert("manifest".to_string(), serde_json::Value::String(result.manifest.to_string()));
+    obj.insert("issue".to_string(), serde_json::Value::String(issue.to_string()));
+    obj.insert(
+        "licenses".to_string(),
+        serde_json::Value::Array(
+            result.licenses.iter().cloned().map(serde_json::Value::String).collect(),
+        ),
+    );
+    if let PackageIssue::HeaderMismatch { uncovered } = &result.issue {
+        obj.insert(
+            "uncovered".to_string(),
+            serde_json::Value::Array(
+                uncovered.iter().cloned().map(serde_json::Value::String).collect(),
+            ),
+        );
+    }
+    serde_json::Value::Object(obj)
+}
+
+fn foreign_license_json(result: &ScanResult) -> serde_json::Value {
+    let mut obj = serde_json::Map::new();
+    obj.insert("path".to_string(), serde_json::Value::String(result.path.to_string()));
+    if let FileStatus::ForeignLicense { detected } = &result.status {
+        obj.insert("detected".to_string(), serde_json::Value::String(detected.clone()));
+    }
+    serde_json::Value::Object(obj)
 }
 
 fn print_json(summary: &ScanSummary) {
@@ -177,10 +478,72 @@ fn print_json(summary: &ScanSummary) {
     summary_obj.insert("passed".to_string(), serde_json::Value::Number(summary.passed.into()));
     summary_obj.insert("failed".to_string(), serde_json::Value::Number(summary.failed.into()));
     summary_obj.insert("skipped".to_string(), serde_json::Value::Number(summary.skipped.into()));
+    summary_obj
+        .insert("too_large".to_string(), serde_json::Value::Number(summary.too_large.into()));
+    summary_obj.insert(
+        "unreadable".to_string(),
+        serde_json::Value::Number(summary.unreadable.len().into()),
+    );
+    summary_obj.insert(
+        "suppressed".to_string(),
+        serde_json::Value::Number(summary.suppressed.len().into()),
+    );
+    summary_obj.insert(
+        "third_party".to_string(),
+        serde_json::Value::Number(summary.third_party.len().into()),
+    );
+    summary_obj.insert(
+        "foreign_licenses".to_string(),
+        serde_json::Value::Number(summary.foreign_licenses.len().into()),
+    );
+    for (key, verdict) in
+        [("policy_denied", PolicyVerdict::Denied), ("policy_review", PolicyVerdict::Review)]
+    {
+        let count = summary.policy.iter().filter(|d| d.verdict == verdict).count();
+        summary_obj.insert(key.to_string(), serde_json::Value::Number(count.into()));
+    }
+    let holders_failed = summary.copyright.iter().filter(|c| c.issue.is_failure()).count();
+    summary_obj
+        .insert("copyright_flagged".to_string(), serde_json::Value::Number(holders_failed.into()));
+    summary_obj.insert(
+        "copyright_non_canonical".to_string(),
+        serde_json::Value::Number(summary.copyright.len().saturating_sub(holders_failed).into()),
+    );
+    summary_obj.insert(
+        "packages_flagged".to_string(),
+        serde_json::Value::Number(summary.packages.len().into()),
+    );
 
     let mut root_obj = serde_json::Map::new();
     root_obj.insert("summary".to_string(), serde_json::Value::Object(summary_obj));
     root_obj.insert("results".to_string(), serde_json::Value::Array(Vec::new()));
+    root_obj.insert(
+        "unreadable".to_string(),
+        serde_json::Value::Array(summary.unreadable.iter().map(unreadable_json).collect()),
+    );
+    root_obj.insert(
+        "suppressed".to_string(),
+        serde_json::Value::Array(summary.suppressed.iter().map(suppressed_json).collect()),
+    );
+    root_obj.insert("policy".to_string(), policy_json(summary));
+    root_obj.insert(
+        "copyright".to_string(),
+        serde_json::Value::Array(summary.copyright.iter().map(copyright_json).collect()),
+    );
+    root_obj.insert(
+        "packages".to_string(),
+        serde_json::Value::Array(summary.packages.iter().map(package_json).collect()),
+    );
+    root_obj.insert(
+        "foreign_licenses".to_string(),
+        serde_json::Value::Array(
+            summary.foreign_licenses.iter().map(foreign_license_json).collect(),
+        ),
+    );
+    root_obj.insert(
+        "third_party".to_string(),
+        serde_json::Value::Array(summary.third_party.iter().map(third_party_json).collect()),
+    );
 
     let json = serde_json::Value::Object(root_obj);
 
@@ -190,9 +553,101 @@ fn print_json(summary: &ScanSummary) {
     }
 }
 
-fn print_github(summary: &ScanSummary) {
+fn print_github(summary: &ScanSummary, list_suppressions: bool) {
     let mut stdout = std::io::stdout().lock();
 
+    // Suppressions are notices: they don't fail the run
+    if list_suppressions {
+        for result in &summary.suppressed {
+            if let Some(reason) = result.status.skip_reason() {
+                write_fmt_stdout(
+                    &mut stdout,
+                    format_args!("::notice file={},title=Suppressed::{}\n", result.path, reason),
+                );
+            }
+        }
+    }
+
+    // One annotation per unreadable file, as errors when they count as failures
+    for result in &summary.unreadable {
+        let level = if result.status.is_unreadable() { "error" } else { "warning" };
+        if let Some(reason) = result.status.access_error() {
+            write_fmt_stdout(
+                &mut stdout,
+                format_args!(
+                    "::{} file={},title=Unreadable File::{}\n",
+                    level, result.path, reason
+                ),
+            );
+        }
+    }
+
+    // Policy decisions: denied licenses fail, review-required ones warn
+    for decision in &summary.policy {
+        let level = match decision.verdict {
+            PolicyVerdict::Denied => "error",
+            PolicyVerdict::Review => "warning",
+            PolicyVerdict::Allowed => continue,
+        };
+        write_fmt_stdout(
+            &mut stdout,
+            format_args!(
+                "::{} file={},title=License Policy::{} is {} by the license policy\n",
+                level,
+                decision.path,
+                decision.license,
+                if level == "error" { "denied" } else { "marked for review" }
+            ),
+        );
+    }
+
+    // Disallowed holders fail, spelling variants warn
+    for result in &summary.copyright {
+        let level = if result.issue.is_failure() { "error" } else { "warning" };
+        write_fmt_stdout(
+            &mut stdout,
+            format_args!(
+                "::{} file={},title=Copyright Holder::{} ({})\n",
+                level, result.path, result.holder, result.issue
+            ),
+        );
+    }
+
+    for result in &summary.packages {
+        write_fmt_stdout(
+            &mut stdout,
+            format_args!(
+                "::error file={},title=Package License::{}\n",
+                result.manifest, result.issue
+            ),
+        );
+    }
+
+    for result in &summary.foreign_licenses {
+        if let FileStatus::ForeignLicense { detected } = &result.status {
+            write_fmt_stdout(
+                &mut stdout,
+                format_args!(
+                    "::error file={},title=Foreign License::Found {} instead of the expected header\n",
+                    result.path, detected
+                ),
+            );
+        }
+    }
+
+    // Third-party files whose license the rule does not accept
+    for result in &summary.third_party {
+        if let FileStatus::ThirdParty { license, allowed: false } = &result.status {
+            write_fmt_stdout(
+                &mut stdout,
+                format_args!(
+                    "::error file={},title=Third-Party License::{} is not allowed here\n",
+                    result.path, license
+                ),
+            );
+        }
+    }
+
     // GitHub Actions annotations format
     if summary.failed > 0 {
         write_fmt_stdout(&mut stdout, format_args!(
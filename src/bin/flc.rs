@@ -4,7 +4,7 @@
 //! Scans directories to find files missing license headers and can automatically
 //! add them with proper comment styles for different file types.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 /// Fast License Checker - Blazing fast license header verification
@@ -20,6 +20,10 @@ pub struct Cli {
     #[arg(short, long)]
     pub fix: bool,
 
+    /// In fix mode, also replace headers that carry a different, recognised license
+    #[arg(long, requires = "fix")]
+    pub force: bool,
+
     /// Path to file containing license header text
     #[arg(short = 'l', long = "license")]
     pub license_file: Option<PathBuf>,
@@ -28,20 +32,57 @@ pub struct Cli {
     #[arg(long = "header", conflicts_with = "license_file")]
     pub header_text: Option<String>,
 
-    /// Config file path
-    #[arg(short, long, default_value = ".license-checker.toml")]
-    pub config: PathBuf,
+    /// Config file path, replacing PATH/.flc.toml (otherwise .flc.toml and friends are
+    /// searched upwards from PATH)
+    #[arg(short, long, global = true)]
+    pub config: Option<PathBuf>,
 
     /// Number of parallel jobs (default: number of CPUs)
     #[arg(short, long, env = "FLC_JOBS")]
     pub jobs: Option<usize>,
 
-    /// Maximum bytes to read for header check
-    #[arg(long, default_value = "8192", env = "FLC_MAX_BYTES")]
-    pub max_bytes: usize,
+    /// Maximum bytes to read for header check [default: 8192]
+    #[arg(long, env = "FLC_MAX_BYTES")]
+    pub max_bytes: Option<usize>,
+
+    /// Only check files matching these globs, relative to the scan root (repeatable)
+    #[arg(long = "include", value_name = "GLOB")]
+    pub include_patterns: Vec<String>,
+
+    /// Only check files with these extensions (comma-separated or repeated)
+    #[arg(long = "only-ext", value_name = "EXT", value_delimiter = ',')]
+    pub only_extensions: Vec<String>,
+
+    /// Only check files of these languages, e.g. rust,python (comma-separated or repeated)
+    #[arg(long = "only-lang", value_name = "LANG", value_delimiter = ',')]
+    pub only_languages: Vec<String>,
+
+    /// Check hidden files and directories (e.g. .github/)
+    #[arg(long)]
+    pub hidden: bool,
+
+    /// Follow symbolic links (cycles are detected and skipped)
+    #[arg(short = 'L', long)]
+    pub follow_symlinks: bool,
+
+    /// Maximum directory depth to descend below the scan root
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+
+    /// Don't respect .gitignore, the global gitignore or .git/info/exclude
+    #[arg(long)]
+    pub no_gitignore: bool,
+
+    /// Name of ignore files honoured in every directory [default: .flcignore]
+    #[arg(long, value_name = "NAME")]
+    pub ignore_file: Option<String>,
+
+    /// Skip files larger than this many bytes
+    #[arg(long, env = "FLC_MAX_FILE_SIZE")]
+    pub max_file_size: Option<u64>,
 
     /// Output format
-    #[arg(short, long, value_enum, default_value = "text")]
+    #[arg(short, long, value_enum, default_value = "text", global = true)]
     pub output: OutputFormat,
 
     /// Increase verbosity (-v, -vv, -vvv)
@@ -55,28 +96,131 @@ pub struct Cli {
     /// Don't use colors in output
     #[arg(long)]
     pub no_color: bool,
+
+    /// Treat unreadable files (permission denied, I/O errors, broken symlinks) as failures
+    #[arg(long)]
+    pub fail_on_unreadable: bool,
+
+    /// Only honour flc:ignore-file / flc:disable markers that carry a justification
+    #[arg(long)]
+    pub require_suppression_reason: bool,
+
+    /// Check that every package has a LICENSE or COPYING file matching its headers
+    #[arg(long)]
+    pub check_packages: bool,
+
+    /// Check copyright years and holders against each file's git history
+    #[arg(long)]
+    pub git_history: bool,
+
+    /// List every file skipped by a suppression marker, with its justification
+    #[arg(long)]
+    pub list_suppressions: bool,
+
+    /// Also write a CycloneDX 1.5 bill of materials with file hashes and license evidence
+    #[arg(long, value_name = "FILE", conflicts_with = "fix")]
+    pub cyclonedx: Option<PathBuf>,
+
+    /// Subcommand to run instead of a scan
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands of flc
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create, validate and inspect configuration files
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// List the licenses and copyright holders found in file headers
+    Inventory {
+        /// Report format [default: json with `-o json`, otherwise text]
+        #[arg(long, value_enum)]
+        format: Option<InventoryFormat>,
+
+        /// Write the report to this file instead of stdout
+        #[arg(long = "output-file", value_name = "FILE")]
+        output_file: Option<PathBuf>,
+    },
+    /// Generate a NOTICE listing third-party files by license
+    Notice {
+        /// Notice format
+        #[arg(long, value_enum, default_value = "text")]
+        format: NoticeFileFormat,
+
+        /// Write the notice to this file [default: NOTICE or THIRD_PARTY_LICENSES.md in PATH]
+        #[arg(long = "output-file", value_name = "FILE")]
+        output_file: Option<PathBuf>,
+
+        /// Fail if the notice file is missing or out of date instead of writing it
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::ExitCode;
 use tracing_subscriber::{fmt, EnvFilter};
 
 mod cli {
+    pub mod config;
+    pub mod inventory;
+    pub mod notice;
     pub mod output;
 }
 
+use cli::config::ConfigCommand;
+use cli::inventory::InventoryFormat;
+use cli::notice::NoticeFileFormat;
 use cli::output::OutputFormat;
 use fast_license_checker::{
-    config::Config, fixer::HeaderFixer, scanner::Scanner, types::ScanSummary,
+    config::{CliOverrides, Config},
+    error::{ExitStatus, LicenseCheckerError},
+    fixer::HeaderFixer,
+    scanner::Scanner,
+    types::ScanSummary,
 };
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    match run(&cli) {
+        Ok(status) => status.into(),
+        Err(err) => {
+            let _ = writeln!(std::io::stderr(), "Error: {err:?}");
+            exit_status_for(&err).into()
+        }
+    }
+}
+
+/// Maps an error to its exit status via the first library error in its chain.
+///
+/// Errors raised by the binary itself (unreadable license file, missing header
+/// text) are usage errors.
+fn exit_status_for(err: &anyhow::Error) -> ExitStatus {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<LicenseCheckerError>())
+        .map_or(ExitStatus::ConfigError, LicenseCheckerError::exit_status)
+}
+
+fn run(cli: &Cli) -> Result<ExitStatus> {
     // Initialize tracing based on verbosity
     init_tracing(cli.verbose, cli.quiet)?;
 
+    match &cli.command {
+        Some(Command::Config(command)) => return cli::config::run(cli, command),
+        Some(Command::Inventory { format, output_file }) => {
+            return cli::inventory::run(cli, *format, output_file.as_deref());
+        }
+        Some(Command::Notice { format, output_file, check }) => {
+            return cli::notice::run(cli, *format, output_file.as_deref(), *check);
+        }
+        None => {}
+    }
+
     // Load configuration
-    let config = load_config(&cli).context("Failed to load configuration")?;
+    let config = load_config(cli).context("Failed to load configuration")?;
 
     tracing::debug!(?config, "Loaded configuration");
 
@@ -89,18 +233,12 @@ fn main() -> Result<()> {
     }
 
     // Run scan or fix
-    let summary =
-        if cli.fix { run_fix_mode(&cli, &config)? } else { run_scan_mode(&cli, &config)? };
+    let summary = if cli.fix { run_fix_mode(cli, &config)? } else { run_scan_mode(cli, &config)? };
 
     // Print results
-    cli::output::print_summary(&summary, cli.output, !cli.no_color);
+    cli::output::print_summary(&summary, cli.output, !cli.no_color, cli.list_suppressions);
 
-    // Exit with error code if there were failures
-    if summary.failed > 0 {
-        std::process::exit(1);
-    }
-
-    Ok(())
+    Ok(ExitStatus::from_summary(&summary))
 }
 
 fn init_tracing(verbose: u8, quiet: bool) -> Result<()> {
@@ -122,17 +260,22 @@ fn init_tracing(verbose: u8, quiet: bool) -> Result<()> {
 }
 
 fn run_scan_mode(cli: &Cli, config: &Config) -> Result<ScanSummary> {
-    let scanner =
-        Scanner::new(cli.path.as_path(), config.clone()).context("Failed to create scanner")?;
+    let scanner = Scanner::new(cli.path.as_path(), config.clone())
+        .context("Failed to create scanner")?
+        .with_evidence(cli.cyclonedx.is_some());
 
     let summary = scanner.scan()?;
+    if let Some(path) = &cli.cyclonedx {
+        cli::inventory::write_cyclonedx(cli, &summary, path)?;
+    }
 
     Ok(summary)
 }
 
 fn run_fix_mode(cli: &Cli, config: &Config) -> Result<ScanSummary> {
-    let fixer =
-        HeaderFixer::new(cli.path.as_path(), config.clone()).context("Failed to create fixer")?;
+    let fixer = HeaderFixer::new(cli.path.as_path(), config.clone())
+        .context("Failed to create fixer")?
+        .with_force(cli.force);
 
     let summary = fixer.fix_all().context("Fix operation failed")?;
 
@@ -140,23 +283,39 @@ fn run_fix_mode(cli: &Cli, config: &Config) -> Result<ScanSummary> {
 }
 
 fn load_config(cli: &Cli) -> Result<Config> {
-    use fast_license_checker::config::{load_config, CliOverrides};
-    use std::fs;
+    use fast_license_checker::config::load_config_from;
+
+    // Without --config, discover the configuration upwards from the scanned path
+    Ok(load_config_from(&cli.path, cli.config.as_deref(), cli_overrides(cli)?)?)
+}
 
+/// Collects the configuration overrides given on the command line
+fn cli_overrides(cli: &Cli) -> Result<CliOverrides> {
     // Load license header from file or text
     let license_header = if let Some(file_path) = &cli.license_file {
-        Some(fs::read_to_string(file_path).context("Failed to read license file")?)
+        Some(std::fs::read_to_string(file_path).context("Failed to read license file")?)
     } else {
         cli.header_text.clone()
     };
 
-    let overrides = CliOverrides {
+    Ok(CliOverrides {
         license_header,
         license_file: cli.license_file.clone(),
         parallel_jobs: cli.jobs,
-        max_header_bytes: Some(cli.max_bytes),
+        max_header_bytes: cli.max_bytes,
         similarity_threshold: None, // CLI doesn't override this yet
-    };
-
-    Ok(load_config(Some(cli.config.as_path()), overrides)?)
+        max_file_size: cli.max_file_size,
+        include_hidden: cli.hidden.then_some(true),
+        follow_symlinks: cli.follow_symlinks.then_some(true),
+        max_depth: cli.max_depth,
+        respect_gitignore: cli.no_gitignore.then_some(false),
+        ignore_filename: cli.ignore_file.clone(),
+        include_patterns: cli.include_patterns.clone(),
+        only_extensions: cli.only_extensions.clone(),
+        only_languages: cli.only_languages.clone(),
+        fail_on_unreadable: cli.fail_on_unreadable.then_some(true),
+        require_suppression_reason: cli.require_suppression_reason.then_some(true),
+        check_packages: cli.check_packages.then_some(true),
+        git_history: cli.git_history.then_some(true),
+    })
 }
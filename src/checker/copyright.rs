@@ -0,0 +1,572 @@
+//! Copyright statement extraction.
+//!
+//! Finds `Copyright (c) 2020 Holder` style lines in the header region of a
+//! file, strips their comment decoration and parses them into years and holder.
+//! [`HolderPolicy`] checks the holders against the `[copyright]` configuration
+//! and, with `git_history`, the years and holders against the file's commits.
+
+use crate::checker::history::FileHistory;
+use crate::checker::prelude::effective_header_start;
+use crate::config::CopyrightConfig;
+use crate::types::CopyrightIssue;
+
+/// Lines of the header region searched for copyright statements
+pub(crate) const HEADER_LINES: usize = 60;
+
+/// Characters that open comment lines before a statement
+const LEADING_DECORATION: &[char] = &['/', '*', '#', ';', '-', '!', '%', '\'', '"', '<', '{'];
+
+/// Comment closers after a statement
+const TRAILING_DECORATION: &[&str] = &["*/", "-->", "-}", "#", "\"\"\"", "'''"];
+
+/// Phrases that open an attribution line, e.g. "Originally written by ..."
+const ATTRIBUTION_MARKERS: &[&str] = &[
+    "this product includes",
+    "this software includes",
+    "originally written by",
+    "originally developed by",
+    "written by",
+    "developed by",
+    "authored by",
+    "author:",
+    "authors:",
+    "based on",
+    "derived from",
+    "adapted from",
+    "portions of this",
+    "portions copyright",
+];
+
+/// Phrases after the holder that are not part of it
+const HOLDER_END_MARKERS: &[&str] =
+    &["all rights reserved", "licensed ", "spdx-license-identifier", "see license"];
+
+/// Words that mark a holder as an organisation rather than a person
+const ORGANISATION_WORDS: &[&str] = &[
+    "ag",
+    "authors",
+    "bv",
+    "co",
+    "company",
+    "contributors",
+    "corp",
+    "corporation",
+    "developers",
+    "foundation",
+    "gmbh",
+    "group",
+    "inc",
+    "incorporated",
+    "labs",
+    "llc",
+    "llp",
+    "limited",
+    "ltd",
+    "project",
+    "sa",
+    "team",
+    "the",
+    "university",
+];
+
+/// An inclusive range of copyright years, e.g. `2019-2024` or a single `2020`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearRange {
+    /// First year
+    pub first: u16,
+    /// Last year, equal to `first` for a single year
+    pub last: u16,
+}
+
+impl YearRange {
+    /// The smallest range covering both ranges
+    pub fn union(self, other: Self) -> Self {
+        Self { first: self.first.min(other.first), last: self.last.max(other.last) }
+    }
+
+    /// Whether `other` lies within this range
+    pub fn covers(self, other: Self) -> bool {
+        self.first <= other.first && other.last <= self.last
+    }
+}
+
+impl std::fmt::Display for YearRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.first == self.last {
+            write!(f, "{}", self.first)
+        } else {
+            write!(f, "{}-{}", self.first, self.last)
+        }
+    }
+}
+
+/// A parsed copyright statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Copyright {
+    /// The statement without comment markers, as written
+    pub statement: String,
+    /// The years it names, in order
+    pub years: Vec<YearRange>,
+    /// The holder, without years, symbols or `All rights reserved`
+    pub holder: String,
+}
+
+impl Copyright {
+    /// The range spanning every year the statement names, if it names any
+    pub fn year_span(&self) -> Option<YearRange> {
+        self.years.iter().copied().reduce(YearRange::union)
+    }
+}
+
+/// Extract the copyright statements from the header of a file
+///
+/// Returns each statement without comment markers, e.g.
+/// `Copyright (c) 2019-2024 Acme Corp.`, in file order and without duplicates.
+#[tracing::instrument(skip(content))]
+pub fn copyright_statements(content: &[u8]) -> Vec<String> {
+    copyrights(content).into_iter().map(|copyright| copyright.statement).collect()
+}
+
+/// Extract and parse the copyright statements from the header of a file
+#[tracing::instrument(skip(content))]
+pub fn copyrights(content: &[u8]) -> Vec<Copyright> {
+    let start_offset = effective_header_start(content);
+    let region = String::from_utf8_lossy(content.get(start_offset..).unwrap_or(&[]));
+
+    let mut copyrights: Vec<Copyright> = Vec::new();
+    for line in region.lines().take(HEADER_LINES) {
+        if let Some(copyright) = copyright_statement(line).and_then(|s| parse_copyright(&s)) {
+            if !copyrights.iter().any(|known| known.statement == copyright.statement) {
+                copyrights.push(copyright);
+            }
+        }
+    }
+    copyrights
+}
+
+/// Extract attribution lines from the header of a file
+///
+/// Returns lines such as `Originally written by Jane Doe` or `Based on zlib 1.2.13`,
+/// without comment markers, in file order and without duplicates.
+#[tracing::instrument(skip(content))]
+pub fn attribution_statements(content: &[u8]) -> Vec<String> {
+    let start_offset = effective_header_start(content);
+    let region = String::from_utf8_lossy(content.get(start_offset..).unwrap_or(&[]));
+
+    let mut statements: Vec<String> = Vec::new();
+    for line in region.lines().take(HEADER_LINES) {
+        let statement = strip_decoration(line);
+        let lower = statement.to_lowercase();
+        let is_attribution = ATTRIBUTION_MARKERS.iter().any(|marker| lower.starts_with(marker))
+            && copyright_statement(line).is_none();
+        if is_attribution && !statements.iter().any(|known| known == statement) {
+            statements.push(statement.to_string());
+        }
+    }
+    statements
+}
+
+/// The copyright statement on a single line, if it is one
+pub fn copyright_statement(line: &str) -> Option<String> {
+    let statement = strip_decoration(line);
+    let lower = statement.to_lowercase();
+    let is_statement = lower.starts_with("copyright") || statement.starts_with('©');
+    // "Copyright notice" and friends in license texts are not statements
+    (is_statement && copyright_holder(statement).is_some()).then(|| statement.to_string())
+}
+
+/// A line without leading comment markers and trailing comment closers
+fn strip_decoration(line: &str) -> &str {
+    let mut statement = line.trim().trim_start_matches(LEADING_DECORATION).trim();
+    while let Some(closer) = TRAILING_DECORATION.iter().find(|closer| statement.ends_with(*closer))
+    {
+        let end = statement.len().saturating_sub(closer.len());
+        statement = statement.get(..end).unwrap_or_default().trim_end();
+    }
+    statement
+}
+
+/// The holder named by a copyright statement
+///
+/// Strips the `Copyright` keyword, `(c)` / `©` symbols, years and a trailing
+/// `All rights reserved.`: `Copyright (c) 2019-2024 Acme Corp.` gives `Acme Corp`.
+pub fn copyright_holder(statement: &str) -> Option<String> {
+    parse_copyright(statement).map(|copyright| copyright.holder)
+}
+
+/// Parse a copyright statement into its years and holder
+pub fn parse_copyright(statement: &str) -> Option<Copyright> {
+    let mut rest = statement.trim();
+    let mut years = String::new();
+    loop {
+        let lower = rest.to_lowercase();
+        let symbol = ["copyright", "(c)", "©"]
+            .iter()
+            .find(|prefix| lower.starts_with(*prefix))
+            .map(|prefix| prefix.len());
+        // Years, ranges and separators: "2019-2024,", "2020 -"
+        let year_len =
+            rest.find(|c: char| !(c.is_ascii_digit() || "-–,:".contains(c))).unwrap_or(rest.len());
+        let skip = match symbol {
+            Some(len) => len,
+            None if year_len > 0 => {
+                years.push_str(rest.get(..year_len).unwrap_or_default());
+                year_len
+            }
+            None => break,
+        };
+        match rest.get(skip..) {
+            Some(tail) => rest = tail.trim_start(),
+            None => break,
+        }
+    }
+
+    let holder = bare_holder(rest.get(..holder_end(rest)).unwrap_or(rest));
+
+    // Placeholders in license texts, e.g. "<year> <name of author>"
+    let is_placeholder = holder.is_empty()
+        || holder.starts_with('<')
+        || holder.starts_with('[')
+        || holder.to_lowercase().starts_with("notice");
+    (!is_placeholder).then(|| Copyright {
+        statement: statement.trim().to_string(),
+        years: parse_years(&years),
+        holder: holder.to_string(),
+    })
+}
+
+/// Where the holder ends in the text after the years: before `All rights
+/// reserved`, a license sentence on the same line, or the end of its sentence
+fn holder_end(text: &str) -> usize {
+    // ASCII lowercase keeps byte offsets valid for `text`
+    let lower = text.to_ascii_lowercase();
+    let marker = HOLDER_END_MARKERS.iter().filter_map(|marker| lower.find(marker)).min();
+    let sentence = text.match_indices(". ").map(|(index, _)| index).find(|&index| {
+        let (before, after) =
+            (text.get(..index).unwrap_or_default(), text.get(index.saturating_add(2)..));
+        // "J. Doe" and "Acme Co. Ltd" continue the name
+        let last_word = before.rsplit(' ').next().unwrap_or_default();
+        let next_word = after.unwrap_or_default().split(' ').next().unwrap_or_default();
+        let next_word = next_word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        last_word.chars().count() > 1
+            && next_word != "the"
+            && !ORGANISATION_WORDS.contains(&next_word.as_str())
+    });
+    [marker, sentence].into_iter().flatten().min().unwrap_or(text.len())
+}
+
+/// A holder name without surrounding whitespace and trailing `.`, `,` or `;`, as
+/// statements are parsed: `Acme Corp.` gives `Acme Corp`
+pub fn bare_holder(name: &str) -> &str {
+    name.trim().trim_end_matches(['.', ',', ';']).trim()
+}
+
+/// Parse `2019-2024,2022` style year lists, ignoring anything that isn't a year
+fn parse_years(text: &str) -> Vec<YearRange> {
+    text.split([',', ':'])
+        .filter_map(|part| {
+            let mut bounds = part.split(['-', '–']).filter_map(|year| year.trim().parse().ok());
+            let first = bounds.next()?;
+            let last = bounds.next_back().unwrap_or(first);
+            Some(YearRange { first, last })
+        })
+        .collect()
+}
+
+/// Checks copyright holders against the `[copyright]` configuration
+#[derive(Debug, Clone, Copy)]
+pub struct HolderPolicy<'a> {
+    config: &'a CopyrightConfig,
+}
+
+impl<'a> HolderPolicy<'a> {
+    /// The policy of a `[copyright]` section
+    pub fn new(config: &'a CopyrightConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns true if holders, aliases or git history checks are configured
+    pub fn is_active(&self) -> bool {
+        !self.config.is_empty()
+    }
+
+    /// The canonical spelling of `holder`, if it is a known holder or variant
+    pub fn canonical(&self, holder: &str) -> Option<&'a str> {
+        let key = holder_key(holder);
+        let alias = self
+            .config
+            .aliases
+            .iter()
+            .find(|(variant, _)| holder_key(variant) == key)
+            .map(|(_, canonical)| canonical.as_str());
+        alias.or_else(|| {
+            let canonical = self.config.holders.iter().chain(self.config.aliases.values());
+            canonical.map(String::as_str).find(|canonical| holder_key(canonical) == key)
+        })
+    }
+
+    /// What is wrong with `holder`, if anything
+    ///
+    /// `holder` is compared as parsed, so a canonical `Acme Corp.` matches the
+    /// `Acme Corp` of `Copyright 2024 Acme Corp.`.
+    pub fn check(&self, holder: &str) -> Option<CopyrightIssue> {
+        match self.canonical(holder) {
+            Some(canonical) if bare_holder(canonical) == holder => None,
+            Some(canonical) => {
+                Some(CopyrightIssue::NonCanonical { canonical: canonical.to_string() })
+            }
+            // With only aliases configured, any holder is allowed
+            None if self.config.holders.is_empty() => None,
+            None if looks_like_person(holder) => Some(CopyrightIssue::IndividualHolder),
+            None => Some(CopyrightIssue::UnknownHolder),
+        }
+    }
+
+    /// The statement whose years should track the history: the first naming a
+    /// known holder, otherwise the first
+    pub fn primary<'c>(&self, copyrights: &'c [Copyright]) -> Option<&'c Copyright> {
+        copyrights
+            .iter()
+            .find(|copyright| self.canonical(&copyright.holder).is_some())
+            .or_else(|| copyrights.first())
+    }
+
+    /// Compare a file's statements with its git history
+    ///
+    /// The primary statement's years must cover the first and last commit year,
+    /// and every author must be named by a statement unless a known holder or an
+    /// organisation holds the copyright. Returns the holder each issue is about.
+    pub fn check_history(
+        &self,
+        copyrights: &[Copyright],
+        history: &FileHistory,
+    ) -> Vec<(String, CopyrightIssue)> {
+        let Some(primary) = self.primary(copyrights) else {
+            return Vec::new();
+        };
+        let mut issues = Vec::new();
+
+        let span = primary.year_span();
+        if !span.is_some_and(|span| span.covers(history.years)) {
+            let expected = span.map_or(history.years, |span| span.union(history.years));
+            issues.push((
+                primary.holder.clone(),
+                CopyrightIssue::OutdatedYears { expected: expected.to_string() },
+            ));
+        }
+
+        let organisation = copyrights.iter().any(|copyright| {
+            self.canonical(&copyright.holder).is_some() || !looks_like_person(&copyright.holder)
+        });
+        let authors: Vec<String> = history
+            .authors
+            .iter()
+            .filter(|author| {
+                let key = holder_key(author);
+                !copyrights.iter().any(|copyright| holder_key(&copyright.holder).contains(&key))
+            })
+            .cloned()
+            .collect();
+        if !organisation && !authors.is_empty() {
+            issues.push((primary.holder.clone(), CopyrightIssue::UncoveredAuthors { authors }));
+        }
+        issues
+    }
+}
+
+/// Holder names compared without case, punctuation or whitespace
+fn holder_key(holder: &str) -> String {
+    holder.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Whether a holder reads like a person's name: two to four capitalised words,
+/// no digits and no organisation suffix, optionally followed by an email address
+fn looks_like_person(holder: &str) -> bool {
+    let name = holder.split('<').next().unwrap_or(holder).trim();
+    let words: Vec<&str> = name.split_whitespace().collect();
+    (2..=4).contains(&words.len())
+        && words.iter().all(|word| {
+            let bare = word.trim_matches(|c: char| !c.is_alphanumeric());
+            bare.chars().next().is_some_and(char::is_uppercase)
+                && !bare.chars().any(|c| c.is_ascii_digit())
+                && !ORGANISATION_WORDS.contains(&bare.to_lowercase().as_str())
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_statements_from_comments() {
+        let content = b"#!/usr/bin/env python3\n# Copyright (c) 2019-2024 Acme Corp.\n\
+                        # Copyright 2021 Jane Doe <jane@example.com>\n#\n# SPDX-License-Identifier: MIT\n";
+        assert_eq!(
+            copyright_statements(content),
+            vec![
+                "Copyright (c) 2019-2024 Acme Corp.",
+                "Copyright 2021 Jane Doe <jane@example.com>"
+            ]
+        );
+
+        let content = b"/*\n * \xC2\xA9 2020, 2022 Example GmbH. All rights reserved.\n */\n";
+        assert_eq!(
+            copyright_statements(content),
+            vec!["© 2020, 2022 Example GmbH. All rights reserved."]
+        );
+    }
+
+    #[test]
+    fn ignores_license_text_mentions() {
+        let content =
+            b"// The above copyright notice and this permission notice shall be included\n\
+                        // Copyright (C) <year>  <name of author>\nfn main() {}\n";
+        assert!(copyright_statements(content).is_empty());
+    }
+
+    #[test]
+    fn extracts_attributions() {
+        let content = b"/*\n * Copyright (c) 2010 Jane Doe\n * Originally written by Jane Doe.\n\
+                        * Based on zlib 1.2.13 */\nint main() { /* developed by nobody */ }\n";
+        assert_eq!(
+            attribution_statements(content),
+            vec!["Originally written by Jane Doe.", "Based on zlib 1.2.13"]
+        );
+    }
+
+    #[test]
+    fn checks_history() {
+        let config: CopyrightConfig = toml::from_str(r#"holders = ["Acme Corp"]"#).unwrap();
+        let holders = HolderPolicy::new(&config);
+        let history = |first, last, authors: &[&str]| FileHistory {
+            years: YearRange { first, last },
+            authors: authors.iter().map(ToString::to_string).collect(),
+        };
+        let parsed = |statements: &[&str]| -> Vec<Copyright> {
+            statements.iter().filter_map(|statement| parse_copyright(statement)).collect()
+        };
+
+        let acme = parsed(&["Copyright 2019-2024 Acme Corp", "Copyright 2010 Jane Doe"]);
+        assert!(holders.check_history(&acme, &history(2019, 2024, &["John Roe"])).is_empty());
+        assert_eq!(
+            holders.check_history(&acme, &history(2018, 2026, &[])),
+            vec![(
+                "Acme Corp".to_string(),
+                CopyrightIssue::OutdatedYears { expected: "2018-2026".to_string() }
+            )]
+        );
+
+        let person = parsed(&["Copyright (c) Jane Doe <jane@example.com>"]);
+        assert_eq!(
+            holders.check_history(&person, &history(2021, 2021, &["Jane Doe", "John Roe"])),
+            vec![
+                (
+                    "Jane Doe <jane@example.com>".to_string(),
+                    CopyrightIssue::OutdatedYears { expected: "2021".to_string() }
+                ),
+                (
+                    "Jane Doe <jane@example.com>".to_string(),
+                    CopyrightIssue::UncoveredAuthors { authors: vec!["John Roe".to_string()] }
+                ),
+            ]
+        );
+        assert!(holders.check_history(&[], &history(2021, 2021, &["John Roe"])).is_empty());
+    }
+
+    #[test]
+    fn canonical_holders_with_trailing_punctuation() {
+        let config: CopyrightConfig = toml::from_str(r#"holders = ["Acme Corp."]"#).unwrap();
+        let holders = HolderPolicy::new(&config);
+        for statement in [
+            "Copyright (c) 2024 Acme Corp.",
+            "Copyright 2020 Acme Corp. All rights reserved.",
+            "Copyright (c) 2024 Acme Corp. Licensed under the MIT License",
+        ] {
+            let holder = copyright_holder(statement).unwrap();
+            assert_eq!(holders.check(&holder), None, "{}", statement);
+        }
+        assert_eq!(
+            holders.check("acme corp"),
+            Some(CopyrightIssue::NonCanonical { canonical: "Acme Corp.".to_string() })
+        );
+    }
+
+    #[test]
+    fn holders() {
+        assert_eq!(copyright_holder("Copyright (c) 2019-2024 Acme Corp.").unwrap(), "Acme Corp");
+        assert_eq!(copyright_holder("Copyright 2021 Jane Doe").unwrap(), "Jane Doe");
+        assert_eq!(
+            copyright_holder("© 2020, 2022 Example GmbH. All rights reserved.").unwrap(),
+            "Example GmbH"
+        );
+        assert_eq!(
+            copyright_holder("Copyright (C) 2020 - 2023 The Authors").unwrap(),
+            "The Authors"
+        );
+        assert_eq!(copyright_holder("Copyright 2024"), None);
+        assert_eq!(
+            copyright_holder("Copyright (c) 2024 Acme Corp. Licensed under the MIT License")
+                .unwrap(),
+            "Acme Corp"
+        );
+        assert_eq!(
+            copyright_holder("Copyright 2024 Acme Corp, licensed under Apache-2.0").unwrap(),
+            "Acme Corp"
+        );
+        assert_eq!(
+            copyright_holder("Copyright 2024 Jane Doe. This file is part of Foo.").unwrap(),
+            "Jane Doe"
+        );
+        assert_eq!(copyright_holder("Copyright 2024 J. R. Doe").unwrap(), "J. R. Doe");
+        assert_eq!(copyright_holder("Copyright 2024 Foo Co. Ltd.").unwrap(), "Foo Co. Ltd");
+    }
+
+    #[test]
+    fn years() {
+        let range = |first, last| YearRange { first, last };
+        let years = |statement: &str| parse_copyright(statement).unwrap().years;
+
+        assert_eq!(years("Copyright (c) 2019-2024 Acme Corp."), vec![range(2019, 2024)]);
+        assert_eq!(years("© 2020, 2022 Example GmbH"), vec![range(2020, 2020), range(2022, 2022)]);
+        assert_eq!(years("Copyright (C) 2020 - 2023 The Authors"), vec![range(2020, 2023)]);
+        assert_eq!(years("Copyright Acme Corp."), vec![]);
+    }
+
+    #[test]
+    fn holder_policy() {
+        let config: CopyrightConfig = toml::from_str(
+            r#"
+            holders = ["Acme Corp", "Jane Doe"]
+            aliases = { "ACME Corporation" = "Acme Corp" }
+            "#,
+        )
+        .unwrap();
+        let policy = HolderPolicy::new(&config);
+
+        assert_eq!(policy.check("Acme Corp"), None);
+        assert_eq!(policy.check("Jane Doe"), None);
+        let non_canonical = Some(CopyrightIssue::NonCanonical { canonical: "Acme Corp".into() });
+        assert_eq!(policy.check("ACME Corporation"), non_canonical);
+        assert_eq!(policy.check("ACME corp"), non_canonical);
+        assert_eq!(policy.check("John Smith"), Some(CopyrightIssue::IndividualHolder));
+        assert_eq!(
+            policy.check("John Smith <john@example.com>"),
+            Some(CopyrightIssue::IndividualHolder)
+        );
+        assert_eq!(policy.check("Initech LLC"), Some(CopyrightIssue::UnknownHolder));
+        assert_eq!(
+            policy.check("The Rust Project Developers"),
+            Some(CopyrightIssue::UnknownHolder)
+        );
+
+        // Aliases alone only normalise spelling
+        let config = CopyrightConfig {
+            aliases: [("Acme Inc.".to_string(), "Acme Corp".to_string())].into(),
+            ..CopyrightConfig::default()
+        };
+        let policy = HolderPolicy::new(&config);
+        assert_eq!(policy.check("Initech LLC"), None);
+        assert!(policy.check("Acme Inc").is_some());
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides algorithms for detecting license headers in source files,
 //! including exact matching and fuzzy matching for malformed headers.
 
-use crate::types::{CommentStyle, LicenseHeader};
+use std::ops::Range;
+
+use crate::checker::prelude::{effective_header_start_for, PreludeLanguage};
+use crate::types::{CommentStyle, LicenseHeader, LineEnding};
 
 /// Result of header detection attempt
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,11 +29,23 @@ pub fn detect_header(
     expected: &LicenseHeader,
     style: &CommentStyle,
 ) -> HeaderMatch {
-    let start_offset = crate::checker::prelude::effective_header_start(content);
+    detect_header_for(content, expected, style, PreludeLanguage::Generic)
+}
+
+/// Check if the expected header is present after the language's prelude
+#[tracing::instrument(skip(content, expected))]
+pub fn detect_header_for(
+    content: &[u8],
+    expected: &LicenseHeader,
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> HeaderMatch {
+    let start_offset = effective_header_start_for(content, language);
     let search_region = content.get(start_offset..).unwrap_or(&[]);
 
-    // Format expected header with comment style
-    let formatted_header = format_header_for_search(expected, style);
+    // Format expected header with comment style, in the file's line ending convention
+    let formatted_header =
+        LineEnding::detect(content).apply(&format_header_for_search(expected, style));
 
     // Exact match
     if search_region.starts_with(formatted_header.as_bytes()) {
@@ -47,9 +62,69 @@ pub fn detect_header(
     HeaderMatch::None
 }
 
+/// The comment block right after the language's prelude, as a byte range
+///
+/// The block is written in `style`: line comments, or a block comment up to its
+/// closing delimiter, plus the blank lines after it. Doc comments (`//!`, `/*!`)
+/// document the code rather than license it, so they end the block. This is the
+/// part of a file classified as a foreign license, and the part `--force` replaces.
+pub fn leading_comment(
+    content: &[u8],
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> Range<usize> {
+    let start = effective_header_start_for(content, language);
+    let region = content.get(start..).unwrap_or(&[]);
+    start..start.saturating_add(leading_comment_len(region, style))
+}
+
+/// Length in bytes of the comment block (and trailing blank lines) starting `region`
+fn leading_comment_len(region: &[u8], style: &CommentStyle) -> usize {
+    let Ok(region) = std::str::from_utf8(region) else {
+        return 0;
+    };
+    let block = style.block_delimiters();
+    let close = block.map_or("", |(_, close)| close);
+    let line_prefix = style.prefix.trim();
+
+    let mut len = 0usize;
+    let mut in_block = false;
+    let mut after_comment = false;
+    for line in region.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if in_block {
+            in_block = !trimmed.contains(close);
+        } else if is_doc_comment(trimmed) {
+            break;
+        } else if after_comment || trimmed.is_empty() {
+            // Blank lines end the comment; only blank lines may follow
+            if !trimmed.is_empty() || len == 0 {
+                break;
+            }
+            after_comment = true;
+        } else if let Some(rest) = block.and_then(|(open, _)| trimmed.strip_prefix(open)) {
+            in_block = !rest.contains(close);
+        } else if line_prefix.is_empty() || !trimmed.starts_with(line_prefix) {
+            break;
+        }
+        len = len.saturating_add(line.len());
+    }
+    len
+}
+
+/// Whether a trimmed line opens an inner doc comment
+fn is_doc_comment(line: &str) -> bool {
+    line.starts_with("//!") || line.starts_with("/*!")
+}
+
 /// Format a license header for search using the given comment style
 #[tracing::instrument(skip(header))]
 pub fn format_header_for_search(header: &LicenseHeader, style: &CommentStyle) -> String {
+    // Opening/per-line/closing layouts render identically for checking and fixing
+    if style.has_layout() {
+        return style.render_layout(header);
+    }
+
     let header_text = header.as_str();
 
     if let Some(suffix) = &style.suffix {
@@ -141,38 +216,8 @@ pub fn calculate_byte_similarity(a: &[u8], b: &[u8]) -> u8 {
 /// Check if content contains any license header (heuristic)
 #[tracing::instrument(skip(content))]
 pub fn contains_any_license_header(content: &[u8]) -> bool {
-    let start_offset = crate::checker::prelude::effective_header_start(content);
-    let search_region = content.get(start_offset..).unwrap_or(&[]);
-
-    // Look for common license keywords in first few lines
-    let content_str = match std::str::from_utf8(search_region) {
-        Ok(s) => s,
-        Err(_) => return false,
-    };
-
-    let first_lines = content_str.lines().take(10).collect::<Vec<_>>().join("\n");
-
-    // Common license indicators (case-insensitive)
-    let license_keywords = [
-        "copyright",
-        "license",
-        "licensed under",
-        "mit license",
-        "apache license",
-        "gpl",
-        "lgpl",
-        "bsd license",
-        "mozilla public license",
-        "isc license",
-    ];
-
-    for keyword in &license_keywords {
-        if first_lines.to_lowercase().contains(keyword) {
-            return true;
-        }
-    }
-
-    false
+    // Known licenses and unrecognised license text both count
+    crate::checker::licenses::identify_license(content) != crate::types::LicenseClassification::None
 }
 
 #[cfg(test)]
@@ -186,11 +231,11 @@ mod tests {
     }
 
     fn create_line_style() -> CommentStyle {
-        CommentStyle { prefix: "//".to_string(), suffix: None }
+        CommentStyle::line_comment("//")
     }
 
     fn create_block_style() -> CommentStyle {
-        CommentStyle { prefix: "/*".to_string(), suffix: Some("*/".to_string()) }
+        CommentStyle::block_comment("/*", "*/")
     }
 
     #[test]
@@ -215,6 +260,28 @@ mod tests {
         assert_eq!(formatted, expected);
     }
 
+    #[test]
+    fn format_header_for_search_javadoc_layout() {
+        let header = create_test_header();
+        let style = CommentStyle::javadoc_style();
+
+        let formatted = format_header_for_search(&header, &style);
+        let expected = "/*\n * MIT License\n *\n * Copyright 2024 Test\n */\n";
+
+        assert_eq!(formatted, expected);
+    }
+
+    #[test]
+    fn detect_header_banner_layout() {
+        let header = create_test_header();
+        let style = CommentStyle::layout("////", "//", "////");
+
+        let content = "////\n// MIT License\n//\n// Copyright 2024 Test\n////\n\nint main() {}";
+
+        let result = detect_header(content.as_bytes(), &header, &style);
+        assert_eq!(result, HeaderMatch::Exact);
+    }
+
     #[test]
     fn detect_header_exact_match() {
         let header = create_test_header();
@@ -239,6 +306,30 @@ mod tests {
         assert_eq!(result, HeaderMatch::Exact);
     }
 
+    #[test]
+    fn detect_header_after_language_prelude() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let formatted = format_header_for_search(&header, &style);
+        let content = format!("<?php\n{}echo 'hi';", formatted);
+
+        let result = detect_header_for(content.as_bytes(), &header, &style, PreludeLanguage::Php);
+        assert_eq!(result, HeaderMatch::Exact);
+        assert_eq!(detect_header(content.as_bytes(), &header, &style), HeaderMatch::None);
+    }
+
+    #[test]
+    fn detect_header_crlf() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let content = "// MIT License\r\n\r\n// Copyright 2024 Test\r\nfn main() {}\r\n";
+
+        let result = detect_header(content.as_bytes(), &header, &style);
+        assert_eq!(result, HeaderMatch::Exact);
+    }
+
     #[test]
     fn detect_header_no_match() {
         let header = create_test_header();
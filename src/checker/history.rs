@@ -0,0 +1,118 @@
+//! Git history of checked files.
+//!
+//! Runs `git log` once over the scan root and records, for every file it
+//! touched, the years of its first and last commit and the names of its
+//! authors. Only the local repository is read; nothing is fetched. Renames are
+//! not followed, so a moved file's history starts at the move.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::checker::copyright::YearRange;
+
+/// Marks the start of a commit in the `git log` output
+const COMMIT_MARKER: char = '\u{1}';
+
+/// Commit years and authors of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHistory {
+    /// Years of the first and the last commit touching the file
+    pub years: YearRange,
+    /// Author names, after `.mailmap`
+    pub authors: BTreeSet<String>,
+}
+
+/// Commit years and authors of the files under a directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitHistory {
+    files: HashMap<PathBuf, FileHistory>,
+}
+
+impl GitHistory {
+    /// Read the history of the files under `root` with the `git` binary
+    ///
+    /// Returns `None`, after logging why, if `git` is not installed or `root` is not
+    /// inside a work tree. Shallow clones only see the fetched commits.
+    #[tracing::instrument]
+    pub fn load(root: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "core.quotepath=off", "log", "--no-merges", "--no-renames"])
+            .args(["--relative", "--name-only", "--date=format:%Y"])
+            .arg(format!("--format={}%ad%x09%aN", COMMIT_MARKER))
+            .args(["--", "."])
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                Some(Self::parse(root, &String::from_utf8_lossy(&output.stdout)))
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                tracing::warn!(
+                    "Could not read git history of {}: {}",
+                    root.display(),
+                    stderr.trim()
+                );
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Could not run git: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Parse `git log` output whose paths are relative to `root`
+    fn parse(root: &Path, log: &str) -> Self {
+        let mut files: HashMap<PathBuf, FileHistory> = HashMap::new();
+        let mut commit: Option<(u16, &str)> = None;
+        for line in log.lines() {
+            if let Some(header) = line.strip_prefix(COMMIT_MARKER) {
+                let (year, author) = header.split_once('\t').unwrap_or((header, ""));
+                commit = year.trim().parse().ok().map(|year| (year, author.trim()));
+                continue;
+            }
+            let (Some((year, author)), false) = (commit, line.is_empty()) else { continue };
+
+            let history = files.entry(root.join(line)).or_insert_with(|| FileHistory {
+                years: YearRange { first: year, last: year },
+                authors: BTreeSet::new(),
+            });
+            history.years.first = history.years.first.min(year);
+            history.years.last = history.years.last.max(year);
+            if !author.is_empty() {
+                history.authors.insert(author.to_string());
+            }
+        }
+        Self { files }
+    }
+
+    /// The history of a file, if it has been committed
+    pub fn file(&self, path: &Path) -> Option<&FileHistory> {
+        self.files.get(path)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_log_output() {
+        let log = "\u{1}2026\tJane Doe\nsrc/lib.rs\nsrc/main.rs\n\n\
+                   \u{1}2021\tJohn Roe\n\n\
+                   \u{1}2019\tJane Doe\nsrc/lib.rs\n";
+        let history = GitHistory::parse(Path::new("/repo"), log);
+
+        let lib = history.file(Path::new("/repo/src/lib.rs")).unwrap();
+        assert_eq!(lib.years, YearRange { first: 2019, last: 2026 });
+        assert_eq!(lib.authors.iter().collect::<Vec<_>>(), vec!["Jane Doe"]);
+
+        let main = history.file(Path::new("/repo/src/main.rs")).unwrap();
+        assert_eq!(main.years, YearRange { first: 2026, last: 2026 });
+        assert!(history.file(Path::new("/repo/README.md")).is_none());
+    }
+}
@@ -0,0 +1,370 @@
+//! License identification over bundled license texts.
+//!
+//! Classifies the header of a file as a known license by SPDX identifier, by
+//! word-bigram overlap with the bundled notice texts, or by the license's name,
+//! and falls back to a keyword search for unrecognised license text.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::checker::prelude::effective_header_start;
+use crate::types::LicenseClassification;
+
+/// A license recognised by the classifier
+#[derive(Debug)]
+pub struct KnownLicense {
+    /// SPDX identifier
+    pub id: &'static str,
+    /// Normalized name phrases, e.g. "apache license version 2 0"
+    names: &'static [&'static str],
+    /// Standard notice or full text, as placed in file headers
+    text: &'static str,
+}
+
+impl KnownLicense {
+    /// The bundled notice or full text
+    pub fn text(&self) -> &'static str {
+        self.text
+    }
+}
+
+/// Licenses bundled with flc
+pub const KNOWN_LICENSES: [KnownLicense; 12] = [
+    KnownLicense {
+        id: "Apache-2.0",
+        names: &["apache license version 2 0", "apache license v2 0", "apache 2 0 license"],
+        text: include_str!("licenses/Apache-2.0.txt"),
+    },
+    KnownLicense {
+        id: "MIT",
+        names: &["mit license", "licensed under the mit"],
+        text: include_str!("licenses/MIT.txt"),
+    },
+    KnownLicense {
+        id: "BSD-2-Clause",
+        names: &["bsd 2 clause", "simplified bsd license"],
+        text: include_str!("licenses/BSD-2-Clause.txt"),
+    },
+    KnownLicense {
+        id: "BSD-3-Clause",
+        names: &["bsd 3 clause", "new bsd license", "revised bsd license"],
+        text: include_str!("licenses/BSD-3-Clause.txt"),
+    },
+    KnownLicense { id: "ISC", names: &["isc license"], text: include_str!("licenses/ISC.txt") },
+    KnownLicense {
+        id: "MPL-2.0",
+        names: &["mozilla public license v 2 0", "mozilla public license version 2 0"],
+        text: include_str!("licenses/MPL-2.0.txt"),
+    },
+    KnownLicense {
+        id: "GPL-2.0-or-later",
+        names: &["gnu general public license version 2", "gplv2"],
+        text: include_str!("licenses/GPL-2.0-or-later.txt"),
+    },
+    KnownLicense {
+        id: "GPL-3.0-or-later",
+        names: &["gnu general public license version 3", "gplv3"],
+        text: include_str!("licenses/GPL-3.0-or-later.txt"),
+    },
+    KnownLicense {
+        id: "LGPL-2.1-or-later",
+        names: &["gnu lesser general public license version 2 1", "lgplv2 1"],
+        text: include_str!("licenses/LGPL-2.1-or-later.txt"),
+    },
+    KnownLicense {
+        id: "LGPL-3.0-or-later",
+        names: &["gnu lesser general public license version 3", "lgplv3"],
+        text: include_str!("licenses/LGPL-3.0-or-later.txt"),
+    },
+    KnownLicense {
+        id: "AGPL-3.0-or-later",
+        names: &["gnu affero general public license version 3", "agplv3"],
+        text: include_str!("licenses/AGPL-3.0-or-later.txt"),
+    },
+    KnownLicense {
+        id: "EPL-2.0",
+        names: &["eclipse public license 2 0", "eclipse public license v2 0"],
+        text: include_str!("licenses/EPL-2.0.txt"),
+    },
+];
+
+/// Lines of the header region searched for a license
+const HEADER_LINES: usize = 60;
+
+/// Lines searched for generic license keywords
+const KEYWORD_LINES: usize = 10;
+
+/// Share of a license's word bigrams the header must contain
+const MIN_TEXT_OVERLAP: f64 = 0.6;
+
+/// Minimum number of shared bigrams, so one-line mentions don't match a text
+const MIN_SHARED_BIGRAMS: usize = 8;
+
+/// Block comment delimiters recognised around a header
+const BLOCK_COMMENTS: [(&str, &str); 6] = [
+    ("/*", "*/"),
+    ("<!--", "-->"),
+    ("{-", "-}"),
+    ("(*", "*)"),
+    ("\"\"\"", "\"\"\""),
+    ("'''", "'''"),
+];
+
+/// Line comment markers recognised in a header (`*` continues a block comment)
+const LINE_COMMENTS: [&str; 8] = ["//", "#", "--", ";", "%", "*", "REM", "rem"];
+
+/// Common license indicators for text that matches no known license
+const LICENSE_KEYWORDS: [&str; 10] = [
+    "copyright",
+    "license",
+    "licensed under",
+    "mit license",
+    "apache license",
+    "gpl",
+    "lgpl",
+    "bsd license",
+    "mozilla public license",
+    "isc license",
+];
+
+/// Identify the license in the header of a file
+///
+/// Returns `Known` for an SPDX identifier, a bundled license text or a license
+/// name in the leading comment (the earliest one); `Unknown` when license keywords appear but match no known license; and
+/// `None` when the header has no license text. Never returns `Ours`: comparing
+/// against the expected header is the checker's job.
+#[tracing::instrument(skip(content))]
+pub fn identify_license(content: &[u8]) -> LicenseClassification {
+    let start_offset = effective_header_start(content);
+    let region = String::from_utf8_lossy(content.get(start_offset..).unwrap_or(&[]));
+    let header: Vec<&str> = region.lines().take(HEADER_LINES).collect();
+
+    if let Some(id) = header.iter().find_map(|line| spdx_identifier(line)) {
+        return LicenseClassification::Known { id };
+    }
+
+    let words = normalize(&header.join("\n"));
+    let comment_words = || normalize(&header_comment(&header).join("\n"));
+    if let Some(license) = match_text(&words).or_else(|| match_name(&comment_words())) {
+        return LicenseClassification::Known { id: license.id.to_string() };
+    }
+
+    let first_lines = header.iter().take(KEYWORD_LINES).copied().collect::<Vec<_>>().join("\n");
+    let first_lines = first_lines.to_lowercase();
+    if LICENSE_KEYWORDS.iter().any(|keyword| first_lines.contains(keyword)) {
+        LicenseClassification::Unknown
+    } else {
+        LicenseClassification::None
+    }
+}
+
+/// Look up a bundled license by SPDX identifier (case-insensitive)
+pub fn known_license(id: &str) -> Option<&'static KnownLicense> {
+    KNOWN_LICENSES.iter().find(|license| license.id.eq_ignore_ascii_case(id))
+}
+
+/// License identifiers in an SPDX expression, without operators or exceptions
+pub fn license_ids(expression: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    let mut tokens = expression.split(|c: char| c.is_whitespace() || c == '(' || c == ')');
+    while let Some(token) = tokens.next() {
+        match token {
+            "" | "AND" | "OR" => {}
+            // The exception id follows WITH
+            "WITH" => {
+                tokens.next();
+            }
+            id => {
+                if !ids.iter().any(|known| known == id) {
+                    ids.push(id.to_string());
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Extract the expression of an `SPDX-License-Identifier:` line
+pub fn spdx_identifier(line: &str) -> Option<String> {
+    let (_, expression) = line.split_once("SPDX-License-Identifier:")?;
+    let expression =
+        expression.trim().trim_end_matches("*/").trim_end_matches("-->").trim().to_string();
+    (!expression.is_empty()).then_some(expression)
+}
+
+/// The bundled license whose text the header contains best
+///
+/// Among texts above the overlap threshold, the one sharing the most bigrams
+/// wins, so BSD-3-Clause beats BSD-2-Clause when the third clause is present.
+fn match_text(words: &[String]) -> Option<&'static KnownLicense> {
+    let header = bigrams(words);
+    KNOWN_LICENSES
+        .iter()
+        .zip(license_bigrams())
+        .filter_map(|(license, expected)| {
+            let shared = expected.intersection(&header).count();
+            #[allow(clippy::cast_precision_loss)] // Bigram counts are small
+            let overlap = shared as f64 / expected.len().max(1) as f64;
+            (shared >= MIN_SHARED_BIGRAMS && overlap >= MIN_TEXT_OVERLAP)
+                .then_some((license, shared, overlap))
+        })
+        .max_by(|a, b| a.1.cmp(&b.1).then(a.2.total_cmp(&b.2)))
+        .map(|(license, _, _)| license)
+}
+
+/// The bundled license whose name appears first in the header
+fn match_name(words: &[String]) -> Option<&'static KnownLicense> {
+    let text = format!(" {} ", words.join(" "));
+    KNOWN_LICENSES
+        .iter()
+        .filter_map(|license| {
+            let position =
+                license.names.iter().filter_map(|name| text.find(&format!(" {} ", name))).min()?;
+            Some((position, license))
+        })
+        .min_by_key(|(position, _)| *position)
+        .map(|(_, license)| license)
+}
+
+/// The leading comment lines of a header, where license names are looked for
+///
+/// Blank lines are skipped; the first code line or inner doc comment (`//!`,
+/// `/*!`) ends the comment, so names in docs, code or string literals below it
+/// don't count. Text that doesn't start with a comment, such as a license file,
+/// is searched up to its first blank line.
+fn header_comment<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut comment = Vec::new();
+    let mut closer: Option<&str> = None;
+    for &line in lines {
+        let trimmed = line.trim();
+        if let Some(close) = closer {
+            closer = (!trimmed.contains(close)).then_some(close);
+        } else if trimmed.is_empty() {
+            continue;
+        } else if trimmed.starts_with("//!") || trimmed.starts_with("/*!") {
+            break;
+        } else if let Some((open, close)) =
+            BLOCK_COMMENTS.iter().find(|(open, _)| trimmed.starts_with(open))
+        {
+            let rest = trimmed.get(open.len()..).unwrap_or_default();
+            closer = (!rest.contains(close)).then_some(*close);
+        } else if LINE_COMMENTS.iter().all(|marker| !trimmed.starts_with(marker)) {
+            if comment.is_empty() {
+                return lines
+                    .iter()
+                    .copied()
+                    .skip_while(|line| line.trim().is_empty())
+                    .take_while(|line| !line.trim().is_empty())
+                    .collect();
+            }
+            break;
+        }
+        comment.push(line);
+    }
+    comment
+}
+
+/// Word bigrams of each bundled license text, computed once
+fn license_bigrams() -> &'static [HashSet<(String, String)>] {
+    static BIGRAMS: OnceLock<Vec<HashSet<(String, String)>>> = OnceLock::new();
+    BIGRAMS.get_or_init(|| {
+        KNOWN_LICENSES.iter().map(|license| bigrams(&normalize(license.text))).collect()
+    })
+}
+
+/// Lowercase alphanumeric words; comment markers and punctuation drop out
+fn normalize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn bigrams(words: &[String]) -> HashSet<(String, String)> {
+    words
+        .windows(2)
+        .filter_map(|pair| Some((pair.first()?.clone(), pair.get(1)?.clone())))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn commented(text: &str, prefix: &str) -> String {
+        let lines: Vec<String> = text
+            .lines()
+            .map(|line| format!("{} {}", prefix, line).trim_end().to_string())
+            .collect();
+        lines.join("\n")
+    }
+
+    fn known(id: &str) -> LicenseClassification {
+        LicenseClassification::Known { id: id.to_string() }
+    }
+
+    #[test]
+    fn identifies_bundled_texts_in_comments() {
+        for license in &KNOWN_LICENSES {
+            let header = commented(license.text, "//");
+            let content =
+                format!("// Copyright (c) 2020 Someone\n//\n{}\nfn main() {{}}\n", header);
+            assert_eq!(identify_license(content.as_bytes()), known(license.id), "{}", license.id);
+        }
+    }
+
+    #[test]
+    fn identifies_spdx_identifiers() {
+        let content = b"#!/bin/sh\n# SPDX-License-Identifier: MIT OR Apache-2.0\necho hi\n";
+        assert_eq!(identify_license(content), known("MIT OR Apache-2.0"));
+
+        let content = b"/* SPDX-License-Identifier: GPL-2.0-only */\nint x;\n";
+        assert_eq!(identify_license(content), known("GPL-2.0-only"));
+    }
+
+    #[test]
+    fn identifies_license_names() {
+        let content = b"# Licensed under the Apache License, Version 2.0\nimport os\n";
+        assert_eq!(identify_license(content), known("Apache-2.0"));
+
+        let content = b"// Copyright 2019 Foo. Released under the MIT License.\nlet x = 1;\n";
+        assert_eq!(identify_license(content), known("MIT"));
+    }
+
+    #[test]
+    fn matches_license_names_in_the_header_comment_only() {
+        let content = b"// Released under the MIT License, unlike the\n\
+            // Apache License, Version 2.0 code it replaces\nfn main() {}\n";
+        assert_eq!(identify_license(content), known("MIT"));
+
+        let content = b"/*\n * Copyright 2020 Acme Corp\n * Licensed under the MIT License\n */\n\
+            const NOTICE: &str = \"Apache License, Version 2.0\";\n";
+        assert_eq!(identify_license(content), known("MIT"));
+
+        let content = b"// Copyright 2020 Acme Corp\n\
+            //! Wraps a tool under the GNU General Public License version 3\nfn main() {}\n";
+        assert_eq!(identify_license(content), LicenseClassification::Unknown);
+
+        let content = b"fn main() {}\n\n// Licensed under the Apache License, Version 2.0\n";
+        assert_eq!(identify_license(content), LicenseClassification::Unknown);
+    }
+
+    #[test]
+    fn distinguishes_related_licenses() {
+        let bsd2 = commented(known_license("BSD-2-Clause").unwrap().text, "#");
+        assert_eq!(identify_license(bsd2.as_bytes()), known("BSD-2-Clause"));
+
+        let lgpl = commented(known_license("LGPL-3.0-or-later").unwrap().text, " *");
+        assert_eq!(identify_license(lgpl.as_bytes()), known("LGPL-3.0-or-later"));
+    }
+
+    #[test]
+    fn unknown_and_missing_licenses() {
+        let content = b"// Copyright 2020 Acme Corp. All rights reserved.\nfn main() {}\n";
+        assert_eq!(identify_license(content), LicenseClassification::Unknown);
+
+        let content = b"fn main() {\n    println!(\"hello\");\n}\n";
+        assert_eq!(identify_license(content), LicenseClassification::None);
+    }
+}
@@ -3,24 +3,35 @@
 //! Provides the main interface for detecting and validating license headers
 //! in source files, with support for different comment styles and fuzzy matching.
 
+pub mod copyright;
 pub mod detector;
+pub mod history;
+pub mod licenses;
+pub mod policy;
 pub mod prelude;
+pub mod styles;
 pub mod validator;
 
-use std::collections::HashMap;
 use std::path::Path;
 
-use crate::config::Config;
+use crate::config::{Config, ThirdPartyRule};
 use crate::error::{CheckerError, Result};
-use crate::types::{CommentStyle, FileExtension, FileStatus, LicenseHeader, MaxHeaderBytes};
+use crate::types::{
+    CommentStyle, FileStatus, LicenseClassification, LicenseHeader, MaxHeaderBytes,
+};
+
+use self::prelude::PreludeLanguage;
+use self::styles::CommentStyleResolver;
 
 /// Main header checker that coordinates all header detection logic
 #[derive(Debug)]
 pub struct HeaderChecker {
     expected_header: LicenseHeader,
-    comment_styles: HashMap<FileExtension, CommentStyle>,
+    comment_styles: CommentStyleResolver,
     max_bytes: MaxHeaderBytes,
     similarity_threshold: u8,
+    /// The license the expected header itself represents, if recognised
+    own_license: LicenseClassification,
 }
 
 impl HeaderChecker {
@@ -33,66 +44,104 @@ impl HeaderChecker {
         // Validate the license header
         validator::validate_header_format(&expected_header)?;
 
-        // Convert config comment styles to our domain types
-        let mut comment_styles = HashMap::new();
-        for (ext_str, style_config) in &config.comment_styles {
-            let extension = FileExtension::new(ext_str.to_string())?;
-            let style = CommentStyle {
-                prefix: style_config.prefix.clone(),
-                suffix: style_config.suffix.clone(),
-            };
-            comment_styles.insert(extension, style);
-        }
+        // Convert config comment styles (name, glob, extension) to our domain types
+        let comment_styles = CommentStyleResolver::new(config)?;
 
         let max_bytes = MaxHeaderBytes::new(config.max_header_bytes)?;
 
+        let own_license = licenses::identify_license(expected_header.as_str().as_bytes());
+
         Ok(Self {
             expected_header,
             comment_styles,
             max_bytes,
             similarity_threshold: config.similarity_threshold,
+            own_license,
         })
     }
 
     /// Check a single file for license header
     #[tracing::instrument(skip(self, content))]
-    pub fn check_content(&self, content: &[u8], extension: Option<&str>) -> FileStatus {
+    pub fn check_content(&self, content: &[u8], path: &Path) -> FileStatus {
         // Get the appropriate comment style
-        let style = self.get_comment_style(extension);
+        let style = self.get_comment_style(path, content);
+
+        // Detect header presence after the language's prelude
+        let language = PreludeLanguage::detect(path, content);
+        let header_match =
+            detector::detect_header_for(content, &self.expected_header, &style, language);
+        if header_match == detector::HeaderMatch::Exact {
+            return FileStatus::HasHeader;
+        }
 
-        // Detect header presence
-        let header_match = detector::detect_header(content, &self.expected_header, &style);
+        // A recognised license other than ours in the leading comment is worse than a
+        // missing header, even when it is close enough to ours to match fuzzily
+        let block = detector::leading_comment(content, &style, language);
+        let leading = content.get(block).unwrap_or(&[]);
+        if let LicenseClassification::Known { id } = licenses::identify_license(leading) {
+            if !self.is_own_license(&id) {
+                return FileStatus::ForeignLicense { detected: id };
+            }
+        }
 
         // Validate the match and return appropriate status
         validator::validate_header_match(&header_match, self.similarity_threshold)
     }
 
+    /// Whether `id` is the license of the expected header
+    fn is_own_license(&self, id: &str) -> bool {
+        matches!(&self.own_license, LicenseClassification::Known { id: own } if own.eq_ignore_ascii_case(id))
+    }
+
+    /// Check a file covered by a `[[third_party]]` rule
+    ///
+    /// The file passes with our header; otherwise its license is identified and
+    /// judged by the rule.
+    #[tracing::instrument(skip(self, content, rule))]
+    pub fn check_third_party(
+        &self,
+        content: &[u8],
+        path: &Path,
+        rule: &ThirdPartyRule,
+    ) -> FileStatus {
+        let license = match self.check_content(content, path) {
+            FileStatus::HasHeader => LicenseClassification::Ours,
+            _ => licenses::identify_license(content),
+        };
+        let allowed = rule.allows(&license);
+        FileStatus::ThirdParty { license, allowed }
+    }
+
     /// Check a file by path (reads content internally)
     #[tracing::instrument(skip(self))]
     pub fn check_file(&self, path: &Path) -> Result<FileStatus> {
         // Read file content
         let content = self.read_file_content(path)?;
 
-        // Get file extension
-        let extension = path.extension().and_then(|ext| ext.to_str()).map(|s| s.to_lowercase());
-
         // Check the content
-        Ok(self.check_content(&content, extension.as_deref()))
+        Ok(self.check_content(&content, path))
     }
 
-    /// Get the comment style for a file extension
-    #[tracing::instrument(skip(self))]
-    fn get_comment_style(&self, extension: Option<&str>) -> CommentStyle {
-        if let Some(ext) = extension {
-            if let Ok(file_ext) = FileExtension::new(ext.to_string()) {
-                if let Some(style) = self.comment_styles.get(&file_ext) {
-                    return style.clone();
-                }
-            }
+    /// Get the comment style for a file (exact name, then longest glob, then extension,
+    /// then shebang interpreter or modeline language)
+    #[tracing::instrument(skip(self, content))]
+    fn get_comment_style(&self, path: &Path, content: &[u8]) -> CommentStyle {
+        if let Some(style) = self.comment_styles.resolve_for_content(path, content) {
+            return style.clone();
         }
 
         // Default to line comments (//) if no style found
-        CommentStyle { prefix: "//".to_string(), suffix: None }
+        CommentStyle::line_comment("//")
+    }
+
+    /// Get the configured comment style for a file, if any
+    pub fn comment_style_for(&self, path: &Path, content: &[u8]) -> Option<&CommentStyle> {
+        self.comment_styles.resolve_for_content(path, content)
+    }
+
+    /// Get the comment style resolver
+    pub fn styles(&self) -> &CommentStyleResolver {
+        &self.comment_styles
     }
 
     /// Read file content up to the maximum header bytes
@@ -124,6 +173,11 @@ impl HeaderChecker {
         &self.expected_header
     }
 
+    /// Get the license the expected header represents, if recognised
+    pub fn own_license(&self) -> &LicenseClassification {
+        &self.own_license
+    }
+
     /// Get the maximum header bytes
     pub fn max_header_bytes(&self) -> usize {
         self.max_bytes.value()
@@ -148,10 +202,7 @@ mod tests {
         config.similarity_threshold = 50; // Lower threshold for fuzzy matching
                                           // Add a comment style for Rust files
         use crate::config::CommentStyleConfig;
-        config.comment_styles.insert(
-            "rs".to_string(),
-            CommentStyleConfig { prefix: "//".to_string(), suffix: None },
-        );
+        config.comment_styles.insert("rs".to_string(), CommentStyleConfig::line_comment("//"));
         config
     }
 
@@ -176,7 +227,7 @@ mod tests {
         let checker = HeaderChecker::new(&config).unwrap();
 
         let content = b"// MIT License\n\n// Copyright 2024 Test\nfn main() {}";
-        let status = checker.check_content(content, Some("rs"));
+        let status = checker.check_content(content, Path::new("test.rs"));
         assert!(matches!(status, FileStatus::HasHeader));
     }
 
@@ -186,7 +237,7 @@ mod tests {
         let checker = HeaderChecker::new(&config).unwrap();
 
         let content = b"fn main() {\n    println!(\"hello\");\n}";
-        let status = checker.check_content(content, Some("rs"));
+        let status = checker.check_content(content, Path::new("test.rs"));
 
         assert!(matches!(status, FileStatus::MissingHeader));
     }
@@ -198,13 +249,71 @@ mod tests {
 
         // Create content with partial header match
         let content = b"// MIT License\n// Copyright 2024 Wrong\nfn main() {}";
-        let status = checker.check_content(content, Some("rs"));
+        let status = checker.check_content(content, Path::new("test.rs"));
 
         // TODO: Fuzzy matching for malformed headers is not fully implemented yet
         // For now, partial matches are treated as missing headers
         assert!(matches!(status, FileStatus::MissingHeader));
     }
 
+    #[test]
+    fn check_content_foreign_license() {
+        let config = create_test_config();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let content = b"// SPDX-License-Identifier: GPL-2.0-only\nfn main() {}";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::ForeignLicense { detected: "GPL-2.0-only".to_string() });
+
+        let content = b"// Licensed under the Apache License, Version 2.0\nfn main() {}";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::ForeignLicense { detected: "Apache-2.0".to_string() });
+
+        // Our own license in a different form is not foreign
+        let content = b"// SPDX-License-Identifier: MIT\nfn main() {}";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::MissingHeader);
+    }
+
+    #[test]
+    fn check_content_foreign_license_despite_fuzzy_match() {
+        let mut config = create_test_config();
+        config.license_header = "SPDX-License-Identifier: MIT".to_string();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let content = b"// SPDX-License-Identifier: AGPL-3.0-or-later\nfn main() {}";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(
+            status,
+            FileStatus::ForeignLicense { detected: "AGPL-3.0-or-later".to_string() }
+        );
+
+        let content = b"// SPDX-License-Identifier: MIT\nfn main() {}";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::HasHeader);
+    }
+
+    #[test]
+    fn check_content_ignores_licenses_mentioned_below_the_header() {
+        let config = create_test_config();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let content = b"// MIT License\n\n// Copyright 2024 Test\n\
+            //! Talks to the GNU General Public License version 3 tool chain\nfn main() {}\n";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::HasHeader);
+
+        let content = b"// MIT License\n\n// Copyright 2024 Test\n\
+            const NAME: &str = \"Apache License, Version 2.0\";\n";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::HasHeader);
+
+        // Module docs are not a license header either
+        let content = b"//! Talks to the GNU General Public License version 3 tool chain\n";
+        let status = checker.check_content(content, Path::new("test.rs"));
+        assert_eq!(status, FileStatus::MissingHeader);
+    }
+
     #[test]
     fn check_content_after_shebang() {
         let config = create_test_config();
@@ -212,7 +321,7 @@ mod tests {
 
         let content =
             b"#!/usr/bin/env python3\n# MIT License\n\n# Copyright 2024 Test\nprint('hello')";
-        let status = checker.check_content(content, Some("py"));
+        let status = checker.check_content(content, Path::new("test.py"));
 
         assert!(matches!(status, FileStatus::HasHeader));
     }
@@ -259,7 +368,7 @@ mod tests {
         let config = create_test_config();
         let checker = HeaderChecker::new(&config).unwrap();
 
-        let style = checker.get_comment_style(Some("rs"));
+        let style = checker.get_comment_style(Path::new("test.rs"), b"");
         assert_eq!(style.prefix, "//");
         assert_eq!(style.suffix, None);
     }
@@ -269,7 +378,7 @@ mod tests {
         let config = create_test_config();
         let checker = HeaderChecker::new(&config).unwrap();
 
-        let style = checker.get_comment_style(Some("xyz"));
+        let style = checker.get_comment_style(Path::new("test.xyz"), b"");
         // Should default to line comments
         assert_eq!(style.prefix, "//");
         assert_eq!(style.suffix, None);
@@ -280,12 +389,44 @@ mod tests {
         let config = create_test_config();
         let checker = HeaderChecker::new(&config).unwrap();
 
-        let style = checker.get_comment_style(None);
+        let style = checker.get_comment_style(Path::new("LICENSE"), b"");
         // Should default to line comments
         assert_eq!(style.prefix, "//");
         assert_eq!(style.suffix, None);
     }
 
+    #[test]
+    fn get_comment_style_by_file_name() {
+        let config = create_test_config();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let style = checker.get_comment_style(Path::new("Dockerfile"), b"");
+        assert_eq!(style.prefix, "#");
+        assert!(checker.comment_style_for(Path::new("Dockerfile"), b"").is_some());
+        assert!(checker.comment_style_for(Path::new("LICENSE"), b"").is_none());
+    }
+
+    #[test]
+    fn check_content_uses_file_name_style() {
+        let config = create_test_config();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let content = b"# MIT License\n\n# Copyright 2024 Test\nFROM alpine\n";
+        let status = checker.check_content(content, Path::new("Dockerfile"));
+        assert!(matches!(status, FileStatus::HasHeader));
+    }
+
+    #[test]
+    fn check_content_extensionless_script_uses_shebang() {
+        let config = create_test_config();
+        let checker = HeaderChecker::new(&config).unwrap();
+
+        let content =
+            b"#!/usr/bin/env python3\n# MIT License\n\n# Copyright 2024 Test\nprint('deploy')";
+        let status = checker.check_content(content, Path::new("bin/deploy"));
+        assert!(matches!(status, FileStatus::HasHeader));
+    }
+
     #[test]
     fn expected_header() {
         let config = create_test_config();
@@ -0,0 +1,136 @@
+//! License policy evaluation.
+//!
+//! Decides whether a file's license (an SPDX identifier or expression) is
+//! allowed, denied or needs review, given the `[policy]` lists that apply to it.
+
+use crate::config::{PolicyConfig, PolicyPathRule};
+use crate::types::PolicyVerdict;
+
+/// The allow, deny and review lists of one policy scope
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyLists<'a> {
+    allow: &'a [String],
+    deny: &'a [String],
+    review: &'a [String],
+}
+
+impl<'a> From<&'a PolicyConfig> for PolicyLists<'a> {
+    fn from(policy: &'a PolicyConfig) -> Self {
+        Self { allow: &policy.allow, deny: &policy.deny, review: &policy.review }
+    }
+}
+
+impl<'a> From<&'a PolicyPathRule> for PolicyLists<'a> {
+    fn from(rule: &'a PolicyPathRule) -> Self {
+        Self { allow: &rule.allow, deny: &rule.deny, review: &rule.review }
+    }
+}
+
+impl PolicyLists<'_> {
+    /// The verdict of this scope for a single identifier, if it lists it
+    fn verdict(&self, id: &str) -> Option<PolicyVerdict> {
+        let listed = |list: &[String]| list.iter().any(|entry| entry.eq_ignore_ascii_case(id));
+        if listed(self.deny) {
+            Some(PolicyVerdict::Denied)
+        } else if listed(self.review) {
+            Some(PolicyVerdict::Review)
+        } else if listed(self.allow) {
+            Some(PolicyVerdict::Allowed)
+        } else {
+            None
+        }
+    }
+}
+
+/// Evaluate a license expression against policy scopes, most specific first
+///
+/// For `OR` the most permissive alternative counts, for `AND` the strictest term.
+/// `WITH` exceptions are judged by their license.
+pub fn evaluate(expression: &str, scopes: &[PolicyLists<'_>]) -> PolicyVerdict {
+    expression
+        .split(" OR ")
+        .filter_map(|alternative| {
+            alternative.split(" AND ").map(|term| license_verdict(term, scopes)).max()
+        })
+        .min()
+        .unwrap_or(PolicyVerdict::Allowed)
+}
+
+/// The verdict for a single license identifier
+fn license_verdict(term: &str, scopes: &[PolicyLists<'_>]) -> PolicyVerdict {
+    let term = term.trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace());
+    let id = term.split(" WITH ").next().unwrap_or(term).trim();
+
+    if let Some(verdict) = scopes.iter().find_map(|scope| scope.verdict(id)) {
+        return verdict;
+    }
+    // An allow list is exhaustive
+    if scopes.iter().any(|scope| !scope.allow.is_empty()) {
+        PolicyVerdict::Denied
+    } else {
+        PolicyVerdict::Allowed
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn strings(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn deny_beats_review_beats_allow() {
+        let policy = PolicyConfig {
+            allow: strings(&["MIT", "Apache-2.0"]),
+            deny: strings(&["GPL-3.0-only"]),
+            review: strings(&["MPL-2.0"]),
+            ..PolicyConfig::default()
+        };
+        let scopes = [PolicyLists::from(&policy)];
+
+        assert_eq!(evaluate("MIT", &scopes), PolicyVerdict::Allowed);
+        assert_eq!(evaluate("apache-2.0", &scopes), PolicyVerdict::Allowed);
+        assert_eq!(evaluate("MPL-2.0", &scopes), PolicyVerdict::Review);
+        assert_eq!(evaluate("GPL-3.0-only", &scopes), PolicyVerdict::Denied);
+        // Not on the allow list
+        assert_eq!(evaluate("ISC", &scopes), PolicyVerdict::Denied);
+    }
+
+    #[test]
+    fn path_rules_take_precedence() {
+        let policy =
+            PolicyConfig { allow: strings(&["MIT", "AGPL-3.0-only"]), ..PolicyConfig::default() };
+        let services = PolicyPathRule {
+            paths: strings(&["services/"]),
+            deny: strings(&["AGPL-3.0-only"]),
+            ..PolicyPathRule::default()
+        };
+
+        let global = [PolicyLists::from(&policy)];
+        let scoped = [PolicyLists::from(&services), PolicyLists::from(&policy)];
+        assert_eq!(evaluate("AGPL-3.0-only", &global), PolicyVerdict::Allowed);
+        assert_eq!(evaluate("AGPL-3.0-only", &scoped), PolicyVerdict::Denied);
+        assert_eq!(evaluate("MIT", &scoped), PolicyVerdict::Allowed);
+    }
+
+    #[test]
+    fn expressions() {
+        let policy = PolicyConfig {
+            deny: strings(&["GPL-2.0-only"]),
+            review: strings(&["LGPL-2.1-only"]),
+            ..PolicyConfig::default()
+        };
+        let scopes = [PolicyLists::from(&policy)];
+
+        assert_eq!(evaluate("MIT OR GPL-2.0-only", &scopes), PolicyVerdict::Allowed);
+        assert_eq!(evaluate("MIT AND GPL-2.0-only", &scopes), PolicyVerdict::Denied);
+        assert_eq!(evaluate("(MIT AND LGPL-2.1-only)", &scopes), PolicyVerdict::Review);
+        assert_eq!(
+            evaluate("GPL-2.0-only WITH Classpath-exception-2.0", &scopes),
+            PolicyVerdict::Denied
+        );
+    }
+}
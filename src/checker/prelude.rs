@@ -2,6 +2,190 @@
 //!
 //! Handles detection of shebang lines, XML declarations, and other content
 //! that may precede license headers in source files.
+//!
+//! Some preludes only make sense for one language (a YAML `---` document start,
+//! Markdown front matter, Go build constraints), so [`effective_header_start_for`]
+//! takes a [`PreludeLanguage`]. Checking and fixing both go through it so they
+//! agree on where the header belongs.
+
+use std::path::Path;
+
+/// UTF-8 byte order mark
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Language-specific prelude rules applied after the generic ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PreludeLanguage {
+    /// Only generic preludes (BOM, shebang, XML declaration, coding/modeline)
+    #[default]
+    Generic,
+    /// PHP `<?php` open tag
+    Php,
+    /// Go `//go:build` and `// +build` constraint blocks
+    Go,
+    /// Python encoding declaration on line 1 or 2
+    Python,
+    /// Dockerfile parser directives (`# syntax=`, `# escape=`, `# check=`)
+    Dockerfile,
+    /// YAML `%` directives and `---` document start
+    Yaml,
+    /// Markdown/Hugo front matter (`---` or `+++` fenced)
+    Markdown,
+}
+
+impl PreludeLanguage {
+    /// Determine the prelude rules from a file name and extension
+    pub fn from_path(path: &Path) -> Self {
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if file_name == "Dockerfile"
+            || file_name == "Containerfile"
+            || file_name.starts_with("Dockerfile.")
+        {
+            return Self::Dockerfile;
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("php" | "phtml" | "php3" | "php4" | "php5" | "phps") => Self::Php,
+            Some("go") => Self::Go,
+            Some("py" | "pyw" | "pyi") => Self::Python,
+            Some("dockerfile") => Self::Dockerfile,
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("md" | "markdown" | "mdx") => Self::Markdown,
+            _ => Self::Generic,
+        }
+    }
+
+    /// Determine the prelude rules from the path, falling back to the shebang
+    /// interpreter or modeline for files the path says nothing about
+    pub fn detect(path: &Path, content: &[u8]) -> Self {
+        match Self::from_path(path) {
+            Self::Generic => match infer_language(content).as_deref() {
+                Some(lang) if lang.starts_with("python") => Self::Python,
+                Some("php") => Self::Php,
+                Some("yaml") => Self::Yaml,
+                Some("markdown") => Self::Markdown,
+                Some("dockerfile") => Self::Dockerfile,
+                _ => Self::Generic,
+            },
+            language => language,
+        }
+    }
+}
+
+/// Detect a UTF-8 byte order mark and return byte offset after it
+#[tracing::instrument]
+pub fn detect_bom(content: &[u8]) -> Option<usize> {
+    content.starts_with(UTF8_BOM).then_some(UTF8_BOM.len())
+}
+
+/// Return the byte offset after the first line (including its newline)
+fn line_end(content: &[u8]) -> usize {
+    memchr::memchr(b'\n', content).and_then(|pos| pos.checked_add(1)).unwrap_or(content.len())
+}
+
+/// Return the first line without its line terminator
+fn first_line(content: &[u8]) -> &[u8] {
+    let line = content.get(..line_end(content)).unwrap_or(content);
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Sum the lengths of leading lines that satisfy `predicate`
+fn leading_lines(content: &[u8], predicate: impl Fn(&[u8]) -> bool) -> usize {
+    let mut offset = 0usize;
+    while let Some(rest) = content.get(offset..).filter(|rest| !rest.is_empty()) {
+        if !predicate(first_line(rest)) {
+            break;
+        }
+        offset = offset.saturating_add(line_end(rest));
+    }
+    offset
+}
+
+/// Detect PHP open tag (`<?php`) and return byte offset after its line
+#[tracing::instrument]
+pub fn detect_php_open_tag(content: &[u8]) -> Option<usize> {
+    let line = first_line(content);
+    (line.len() >= 5 && line.get(..5).is_some_and(|tag| tag.eq_ignore_ascii_case(b"<?php")))
+        .then(|| line_end(content))
+}
+
+/// Detect Go build constraint lines and return byte offset after them
+///
+/// Skips consecutive `//go:build` and `// +build` lines plus the blank line
+/// that must follow them.
+#[tracing::instrument]
+pub fn detect_go_build_constraints(content: &[u8]) -> Option<usize> {
+    let constraints = leading_lines(content, |line| {
+        line.starts_with(b"//go:build") || line.starts_with(b"// +build")
+    });
+    if constraints == 0 {
+        return None;
+    }
+
+    let blank = content
+        .get(constraints..)
+        .map(|rest| leading_lines(rest, |line| line.iter().all(u8::is_ascii_whitespace)))
+        .unwrap_or(0);
+    constraints.checked_add(blank)
+}
+
+/// Detect a Python encoding declaration (PEP 263) on the first line
+#[tracing::instrument]
+pub fn detect_python_encoding(content: &[u8]) -> Option<usize> {
+    let line = std::str::from_utf8(first_line(content)).ok()?;
+    let comment = line.trim_start().strip_prefix('#')?;
+    (comment.contains("coding:") || comment.contains("coding=")).then(|| line_end(content))
+}
+
+/// Detect Dockerfile parser directives and return byte offset after them
+#[tracing::instrument]
+pub fn detect_dockerfile_directives(content: &[u8]) -> Option<usize> {
+    let offset = leading_lines(content, |line| {
+        let Ok(line) = std::str::from_utf8(line) else {
+            return false;
+        };
+        line.strip_prefix('#')
+            .and_then(|directive| directive.split_once('='))
+            .map(|(key, _)| {
+                let key = key.trim().to_ascii_lowercase();
+                key == "syntax" || key == "escape" || key == "check"
+            })
+            .unwrap_or(false)
+    });
+    (offset > 0).then_some(offset)
+}
+
+/// Detect YAML directives and `---` document start, return byte offset after them
+#[tracing::instrument]
+pub fn detect_yaml_document_start(content: &[u8]) -> Option<usize> {
+    let directives = leading_lines(content, |line| line.starts_with(b"%"));
+    let rest = content.get(directives..)?;
+    let line = first_line(rest);
+    let is_start = line == b"---" || line.starts_with(b"--- ") || line.starts_with(b"---\t");
+    is_start.then(|| directives.saturating_add(line_end(rest)))
+}
+
+/// Detect Markdown/Hugo front matter (`---` YAML or `+++` TOML fences)
+#[tracing::instrument]
+pub fn detect_front_matter(content: &[u8]) -> Option<usize> {
+    let fence = first_line(content);
+    if fence != b"---" && fence != b"+++" {
+        return None;
+    }
+
+    let mut offset = line_end(content);
+    while let Some(rest) = content.get(offset..).filter(|rest| !rest.is_empty()) {
+        offset = offset.saturating_add(line_end(rest));
+        if first_line(rest) == fence {
+            return Some(offset);
+        }
+    }
+
+    // Unterminated front matter is not a prelude
+    None
+}
 
 /// Detect shebang line and return byte offset after it
 #[tracing::instrument]
@@ -14,6 +198,97 @@ pub fn detect_shebang(content: &[u8]) -> Option<usize> {
     }
 }
 
+/// Extract the interpreter name from a shebang line
+///
+/// Handles direct paths (`#!/bin/bash`) as well as `env` indirection with
+/// flags and variable assignments (`#!/usr/bin/env -S VAR=1 python3 -u`).
+#[tracing::instrument]
+pub fn shebang_interpreter(content: &[u8]) -> Option<&str> {
+    if !content.starts_with(b"#!") {
+        return None;
+    }
+
+    let end = detect_shebang(content).unwrap_or(content.len());
+    let line = std::str::from_utf8(content.get(2..end)?).ok()?;
+
+    let mut parts = line.split_whitespace();
+    let mut program = program_name(parts.next()?);
+
+    if program == "env" {
+        program =
+            parts.find(|part| !part.starts_with('-') && !part.contains('=')).map(program_name)?;
+    }
+
+    Some(program)
+}
+
+/// Strip the directory part of an interpreter path
+fn program_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Extract the language named by an Emacs or Vim modeline in the first lines
+///
+/// Recognizes `-*- mode: python -*-`, `-*- python -*-`, `vim: set ft=ruby:`
+/// and `vi: filetype=sh`. The returned name is lowercase.
+#[tracing::instrument]
+pub fn modeline_language(content: &[u8]) -> Option<String> {
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text,
+        Err(e) => std::str::from_utf8(content.get(..e.valid_up_to())?).ok()?,
+    };
+
+    text.lines().take(5).find_map(|line| emacs_mode(line).or_else(|| vim_filetype(line)))
+}
+
+/// Infer the language of a file from its shebang interpreter or an Emacs/Vim modeline
+///
+/// Used for files whose name, globs and extension resolve to no comment style,
+/// e.g. `bin/deploy` starting with `#!/usr/bin/env python3`.
+#[tracing::instrument(skip(content))]
+pub fn infer_language(content: &[u8]) -> Option<String> {
+    if let Some(interpreter) = shebang_interpreter(content) {
+        return Some(interpreter.to_lowercase());
+    }
+
+    modeline_language(content)
+}
+
+/// Parse an Emacs `-*- ... -*-` file variables line
+fn emacs_mode(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (vars, _) = rest.split_once("-*-")?;
+
+    if !vars.contains(':') {
+        let mode = vars.trim();
+        return (!mode.is_empty()).then(|| mode.to_lowercase());
+    }
+
+    vars.split(';').find_map(|var| {
+        let (key, value) = var.split_once(':')?;
+        key.trim().eq_ignore_ascii_case("mode").then(|| value.trim().to_lowercase())
+    })
+}
+
+/// Parse a Vim `vim:`/`vi:` modeline for its filetype or syntax option
+fn vim_filetype(line: &str) -> Option<String> {
+    let start = ["vim:", "vi:"].iter().find_map(|marker| {
+        line.match_indices(marker)
+            .find(|(pos, _)| {
+                *pos == 0
+                    || line.get(..*pos).is_some_and(|before| before.ends_with(char::is_whitespace))
+            })
+            .and_then(|(pos, _)| pos.checked_add(marker.len()))
+    })?;
+
+    line.get(start..)?
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .filter_map(|option| option.split_once('='))
+        .find(|(key, _)| matches!(*key, "ft" | "filetype" | "syntax" | "syn"))
+        .map(|(_, value)| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+}
+
 /// Detect XML declaration and return byte offset after it
 #[tracing::instrument]
 pub fn detect_xml_declaration(content: &[u8]) -> Option<usize> {
@@ -58,10 +333,36 @@ pub fn detect_hashbang(content: &[u8]) -> Option<usize> {
 /// Get the effective header start offset considering all possible prefixes
 #[tracing::instrument]
 pub fn effective_header_start(content: &[u8]) -> usize {
-    detect_shebang(content)
-        .or_else(|| detect_xml_declaration(content))
-        .or_else(|| detect_hashbang(content))
-        .unwrap_or(0)
+    effective_header_start_for(content, PreludeLanguage::Generic)
+}
+
+/// Get the header start offset after generic and language-specific preludes
+///
+/// Order: UTF-8 BOM, then one of shebang / XML declaration / coding or vim
+/// line, then the language-specific prelude.
+#[tracing::instrument]
+pub fn effective_header_start_for(content: &[u8], language: PreludeLanguage) -> usize {
+    let mut offset = detect_bom(content).unwrap_or(0);
+
+    let rest = content.get(offset..).unwrap_or(&[]);
+    let generic = detect_shebang(rest)
+        .or_else(|| detect_xml_declaration(rest))
+        .or_else(|| detect_hashbang(rest))
+        .unwrap_or(0);
+    offset = offset.saturating_add(generic);
+
+    let rest = content.get(offset..).unwrap_or(&[]);
+    let specific = match language {
+        PreludeLanguage::Generic => None,
+        PreludeLanguage::Php => detect_php_open_tag(rest),
+        PreludeLanguage::Go => detect_go_build_constraints(rest),
+        PreludeLanguage::Python => detect_python_encoding(rest),
+        PreludeLanguage::Dockerfile => detect_dockerfile_directives(rest),
+        PreludeLanguage::Yaml => detect_yaml_document_start(rest),
+        PreludeLanguage::Markdown => detect_front_matter(rest),
+    };
+
+    offset.saturating_add(specific.unwrap_or(0)).min(content.len())
 }
 
 #[cfg(test)]
@@ -87,6 +388,54 @@ mod tests {
         assert_eq!(detect_shebang(content), None);
     }
 
+    #[test]
+    fn shebang_interpreter_direct_path() {
+        assert_eq!(shebang_interpreter(b"#!/bin/bash\necho hi"), Some("bash"));
+        assert_eq!(shebang_interpreter(b"#!/usr/bin/python3"), Some("python3"));
+    }
+
+    #[test]
+    fn shebang_interpreter_env() {
+        assert_eq!(shebang_interpreter(b"#!/usr/bin/env python3\n"), Some("python3"));
+        assert_eq!(shebang_interpreter(b"#!/usr/bin/env -S VAR=1 node --inspect\n"), Some("node"));
+    }
+
+    #[test]
+    fn shebang_interpreter_none() {
+        assert_eq!(shebang_interpreter(b"print('hello')"), None);
+        assert_eq!(shebang_interpreter(b"#!\n"), None);
+        assert_eq!(shebang_interpreter(b"#!/usr/bin/env\n"), None);
+    }
+
+    #[test]
+    fn modeline_language_emacs() {
+        assert_eq!(
+            modeline_language(b"# -*- mode: Python; coding: utf-8 -*-\n").as_deref(),
+            Some("python")
+        );
+        assert_eq!(modeline_language(b"#!/bin/sh\n# -*- ruby -*-\n").as_deref(), Some("ruby"));
+        assert_eq!(modeline_language(b"# -*- coding: utf-8 -*-\n"), None);
+    }
+
+    #[test]
+    fn modeline_language_vim() {
+        assert_eq!(modeline_language(b"# vim: set ft=ruby:\nputs 'x'").as_deref(), Some("ruby"));
+        assert_eq!(
+            modeline_language(b"// vi: filetype=javascript\n").as_deref(),
+            Some("javascript")
+        );
+        assert_eq!(modeline_language(b"# vim: ts=4 sw=4\n"), None);
+        assert_eq!(modeline_language(b"# envim: ft=ruby\n"), None);
+    }
+
+    #[test]
+    fn infer_language_from_shebang_and_modeline() {
+        assert_eq!(infer_language(b"#!/usr/bin/env python3\nprint(1)").as_deref(), Some("python3"));
+        assert_eq!(infer_language(b"#!/bin/bash").as_deref(), Some("bash"));
+        assert_eq!(infer_language(b"# vim: set ft=ruby:\n").as_deref(), Some("ruby"));
+        assert_eq!(infer_language(b"just text"), None);
+    }
+
     #[test]
     fn test_detect_xml_declaration() {
         let content = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<root>";
@@ -172,4 +521,90 @@ mod tests {
         let content = b"#!/bin/bash\n# -*- coding: utf-8 -*-\ncode";
         assert_eq!(effective_header_start(content), 12);
     }
+
+    #[test]
+    fn effective_header_start_bom() {
+        let content = b"\xEF\xBB\xBF#!/bin/bash\necho hello";
+        assert_eq!(effective_header_start(content), 15);
+        assert_eq!(effective_header_start(b"\xEF\xBB\xBFcode"), 3);
+    }
+
+    #[test]
+    fn prelude_php_open_tag() {
+        let content = b"<?php\necho 'hi';";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Php), 6);
+        assert_eq!(effective_header_start(content), 0);
+
+        let content = b"#!/usr/bin/env php\n<?php\necho 'hi';";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Php), 25);
+    }
+
+    #[test]
+    fn prelude_go_build_constraints() {
+        let content = b"//go:build linux\n// +build linux\n\npackage main";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Go), 34);
+        assert_eq!(effective_header_start_for(b"package main", PreludeLanguage::Go), 0);
+    }
+
+    #[test]
+    fn prelude_python_encoding_on_line_two() {
+        let content = b"#!/usr/bin/env python\n# -*- coding: latin-1 -*-\nprint(1)";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Python), 48);
+
+        let content = b"# coding=utf-8\nprint(1)";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Python), 15);
+    }
+
+    #[test]
+    fn prelude_dockerfile_directives() {
+        let content = b"# syntax=docker/dockerfile:1\n# escape=`\nFROM alpine";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Dockerfile), 40);
+
+        let content = b"# Build image\nFROM alpine";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Dockerfile), 0);
+    }
+
+    #[test]
+    fn prelude_yaml_document_start() {
+        let content = b"%YAML 1.2\n---\nkey: value";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Yaml), 14);
+        assert_eq!(effective_header_start_for(b"---\na: 1\n---\nb: 2", PreludeLanguage::Yaml), 4);
+        assert_eq!(effective_header_start_for(b"key: value", PreludeLanguage::Yaml), 0);
+    }
+
+    #[test]
+    fn prelude_markdown_front_matter() {
+        let content = b"---\ntitle: Post\n---\n# Heading";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Markdown), 20);
+
+        let content = b"+++\ntitle = \"Post\"\n+++\nBody";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Markdown), 23);
+
+        // Unterminated front matter is left alone
+        let content = b"---\ntitle: Post\n";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Markdown), 0);
+    }
+
+    #[test]
+    fn prelude_elixir_shebang() {
+        let content = b"#!/usr/bin/env elixir\nIO.puts(1)";
+        assert_eq!(effective_header_start_for(content, PreludeLanguage::Generic), 22);
+    }
+
+    #[test]
+    fn prelude_language_detection() {
+        assert_eq!(PreludeLanguage::from_path(Path::new("index.php")), PreludeLanguage::Php);
+        assert_eq!(PreludeLanguage::from_path(Path::new("main.go")), PreludeLanguage::Go);
+        assert_eq!(
+            PreludeLanguage::from_path(Path::new("Dockerfile")),
+            PreludeLanguage::Dockerfile
+        );
+        assert_eq!(PreludeLanguage::from_path(Path::new("ci.yml")), PreludeLanguage::Yaml);
+        assert_eq!(PreludeLanguage::from_path(Path::new("post.md")), PreludeLanguage::Markdown);
+        assert_eq!(PreludeLanguage::from_path(Path::new("lib.rs")), PreludeLanguage::Generic);
+        assert_eq!(
+            PreludeLanguage::detect(Path::new("bin/deploy"), b"#!/usr/bin/env python3\n"),
+            PreludeLanguage::Python
+        );
+    }
 }
@@ -0,0 +1,226 @@
+//! Comment style resolution by file name, glob and extension.
+//!
+//! Resolves the comment style for a path with the following precedence:
+//! exact file name, then the longest matching glob, then the file extension.
+//! Files that match none of these fall back to the language inferred from
+//! their shebang interpreter or Emacs/Vim modeline.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::checker::prelude::infer_language;
+use crate::config::Config;
+use crate::error::{ConfigError, Result};
+use crate::types::{CommentStyle, FileExtension};
+
+/// Precompiled lookup table for comment styles
+#[derive(Debug, Default)]
+pub struct CommentStyleResolver {
+    by_name: HashMap<String, CommentStyle>,
+    /// Globs sorted by pattern length (longest first), ties broken by pattern
+    globs: Vec<(String, GlobMatcher, CommentStyle)>,
+    by_extension: HashMap<FileExtension, CommentStyle>,
+    by_interpreter: HashMap<String, CommentStyle>,
+}
+
+impl CommentStyleResolver {
+    /// Build a resolver from the configured comment style tables
+    #[tracing::instrument(skip(config))]
+    pub fn new(config: &Config) -> Result<Self> {
+        let by_name = config
+            .filename_comment_styles
+            .iter()
+            .map(|(name, style)| (name.clone(), CommentStyle::from(style)))
+            .collect();
+
+        let mut globs = Vec::with_capacity(config.glob_comment_styles.len());
+        for (pattern, style) in &config.glob_comment_styles {
+            globs.push((pattern.clone(), compile_glob(pattern)?, CommentStyle::from(style)));
+        }
+        globs.sort_by(|(a, _, _), (b, _, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let mut by_extension = HashMap::new();
+        for (ext_str, style) in &config.comment_styles {
+            let extension = FileExtension::new(ext_str.to_string())?;
+            by_extension.insert(extension, CommentStyle::from(style));
+        }
+
+        let by_interpreter = config
+            .interpreter_comment_styles
+            .iter()
+            .map(|(name, style)| (name.to_lowercase(), CommentStyle::from(style)))
+            .collect();
+
+        Ok(Self { by_name, globs, by_extension, by_interpreter })
+    }
+
+    /// Resolve the comment style for a path
+    pub fn resolve(&self, path: &Path) -> Option<&CommentStyle> {
+        let file_name = path.file_name().and_then(|n| n.to_str());
+
+        if let Some(style) = file_name.and_then(|name| self.by_name.get(name)) {
+            return Some(style);
+        }
+
+        if let Some((_, _, style)) = self.globs.iter().find(|(_, glob, _)| glob.is_match(path)) {
+            return Some(style);
+        }
+
+        self.resolve_extension(path.extension().and_then(|ext| ext.to_str()))
+    }
+
+    /// Resolve the comment style for a path, falling back to the file content
+    ///
+    /// The shebang interpreter or modeline language is only consulted when the
+    /// path itself resolves to no style.
+    pub fn resolve_for_content(&self, path: &Path, content: &[u8]) -> Option<&CommentStyle> {
+        self.resolve(path).or_else(|| self.resolve_language(&infer_language(content)?))
+    }
+
+    /// Resolve the comment style for an interpreter or modeline language name
+    ///
+    /// Versioned interpreters such as `python3.11` fall back to `python`.
+    pub fn resolve_language(&self, language: &str) -> Option<&CommentStyle> {
+        let language = language.to_lowercase();
+        self.by_interpreter.get(&language).or_else(|| {
+            let unversioned = language.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+            self.by_interpreter.get(unversioned)
+        })
+    }
+
+    /// Resolve the comment style for a bare extension
+    pub fn resolve_extension(&self, extension: Option<&str>) -> Option<&CommentStyle> {
+        let file_ext = FileExtension::new(extension?.to_string()).ok()?;
+        self.by_extension.get(&file_ext)
+    }
+}
+
+/// Compile a glob key. Patterns without a `/` match the file name in any
+/// directory; patterns with a `/` match the path below any directory.
+fn compile_glob(pattern: &str) -> Result<GlobMatcher> {
+    let anchored = if pattern.starts_with('/') || pattern.starts_with("**/") {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+
+    let glob = GlobBuilder::new(&anchored).literal_separator(true).build().map_err(|e| {
+        ConfigError::InvalidValue {
+            field: "glob_comment_styles",
+            message: format!("Invalid glob '{}': {}", pattern, e),
+        }
+    })?;
+
+    Ok(glob.compile_matcher())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::CommentStyleConfig;
+
+    fn create_test_config() -> Config {
+        Config::default()
+            .with_filename_comment_style("Jenkinsfile", CommentStyleConfig::line_comment("//"))
+            .with_glob_comment_style("*.ts", CommentStyleConfig::line_comment("#"))
+            .with_glob_comment_style("*.d.ts", CommentStyleConfig::block_comment("/*", "*/"))
+            .with_glob_comment_style("scripts/*.txt", CommentStyleConfig::line_comment(";"))
+    }
+
+    #[test]
+    fn resolve_exact_name() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver.resolve(Path::new("/repo/ci/Jenkinsfile")).unwrap();
+        assert_eq!(style.prefix, "//");
+
+        let style = resolver.resolve(Path::new("/repo/Dockerfile")).unwrap();
+        assert_eq!(style.prefix, "#");
+    }
+
+    #[test]
+    fn resolve_longest_glob_wins() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver.resolve(Path::new("/repo/src/types.d.ts")).unwrap();
+        assert_eq!(style.prefix, "/*");
+
+        let style = resolver.resolve(Path::new("/repo/src/main.ts")).unwrap();
+        assert_eq!(style.prefix, "#");
+    }
+
+    #[test]
+    fn resolve_glob_with_directory() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver.resolve(Path::new("/repo/scripts/notes.txt")).unwrap();
+        assert_eq!(style.prefix, ";");
+
+        assert!(resolver.resolve(Path::new("/repo/docs/notes.txt")).is_none());
+    }
+
+    #[test]
+    fn resolve_falls_back_to_extension() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver.resolve(Path::new("/repo/src/lib.RS")).unwrap();
+        assert_eq!(style.prefix, "//");
+
+        assert!(resolver.resolve(Path::new("/repo/unknown.xyz")).is_none());
+        assert!(resolver.resolve(Path::new("/repo/LICENSE")).is_none());
+    }
+
+    #[test]
+    fn resolve_for_content_uses_shebang() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver
+            .resolve_for_content(Path::new("/repo/bin/deploy"), b"#!/usr/bin/env python3.11\n")
+            .unwrap();
+        assert_eq!(style.prefix, "#");
+
+        let style =
+            resolver.resolve_for_content(Path::new("/repo/bin/serve"), b"#!/usr/bin/node\n");
+        assert_eq!(style.unwrap().prefix, "//");
+    }
+
+    #[test]
+    fn resolve_for_content_uses_modeline() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver
+            .resolve_for_content(Path::new("/repo/tool"), b"-- vim: set ft=lua:\nprint(1)\n")
+            .unwrap();
+        assert_eq!(style.prefix, "--");
+    }
+
+    #[test]
+    fn resolve_for_content_prefers_path() {
+        let resolver = CommentStyleResolver::new(&create_test_config()).unwrap();
+
+        let style = resolver
+            .resolve_for_content(Path::new("/repo/ci/Jenkinsfile"), b"#!/bin/bash\n")
+            .unwrap();
+        assert_eq!(style.prefix, "//");
+    }
+
+    #[test]
+    fn resolve_language_configurable() {
+        let config = create_test_config()
+            .with_interpreter_comment_style("mytool", CommentStyleConfig::line_comment(";"));
+        let resolver = CommentStyleResolver::new(&config).unwrap();
+
+        assert_eq!(resolver.resolve_language("MyTool").unwrap().prefix, ";");
+        assert!(resolver.resolve_language("unknown-interpreter").is_none());
+    }
+
+    #[test]
+    fn invalid_glob_is_config_error() {
+        let config = Config::default()
+            .with_glob_comment_style("src/[", CommentStyleConfig::line_comment("//"));
+        assert!(CommentStyleResolver::new(&config).is_err());
+    }
+}
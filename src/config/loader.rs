@@ -2,12 +2,15 @@
 //!
 //! Handles loading configuration from files, CLI arguments, and environment variables.
 
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::config::presets::{preset, preset_names, PRESET_PREFIX};
 use crate::config::types::Config;
-use crate::error::{ConfigError, Result};
+use crate::error::{ConfigError, ConfigLocation, Result};
 
 /// CLI argument overrides for configuration
 #[derive(Debug, Clone, Default)]
@@ -22,6 +25,83 @@ pub struct CliOverrides {
     pub max_header_bytes: Option<usize>,
     /// Override similarity threshold
     pub similarity_threshold: Option<u8>,
+    /// Override maximum file size in bytes
+    pub max_file_size: Option<u64>,
+    /// Override whether hidden files are checked
+    pub include_hidden: Option<bool>,
+    /// Override whether symbolic links are followed
+    pub follow_symlinks: Option<bool>,
+    /// Override maximum walk depth
+    pub max_depth: Option<usize>,
+    /// Override whether gitignore files are respected
+    pub respect_gitignore: Option<bool>,
+    /// Override the per-directory ignore file name
+    pub ignore_filename: Option<String>,
+    /// Additional include patterns (appended to the config file's)
+    pub include_patterns: Vec<String>,
+    /// Additional extensions to restrict checking to
+    pub only_extensions: Vec<String>,
+    /// Additional languages to restrict checking to
+    pub only_languages: Vec<String>,
+    /// Override whether unreadable files count as failures
+    pub fail_on_unreadable: Option<bool>,
+    /// Override whether suppression markers need a justification
+    pub require_suppression_reason: Option<bool>,
+    /// Override whether package license files are checked
+    pub check_packages: Option<bool>,
+    /// Override whether copyright statements are compared with git history
+    pub git_history: Option<bool>,
+}
+
+/// Root configuration file names, in priority order
+pub const CONFIG_FILE_NAMES: [&str; 4] = [".flc.toml", ".flc.json", "flc.toml", "flc.json"];
+
+/// Project manifests that may embed the configuration, in priority order
+///
+/// A manifest is only used when it has an `flc` section: `[package.metadata.flc]` or
+/// `[workspace.metadata.flc]` in `Cargo.toml`, `[tool.flc]` in `pyproject.toml` and
+/// an `"flc"` key in `package.json`.
+pub const MANIFEST_FILE_NAMES: [&str; 3] = ["Cargo.toml", "pyproject.toml", "package.json"];
+
+/// Per-directory configuration file name picked up while walking
+pub const NESTED_CONFIG_FILE: &str = ".flc.toml";
+
+/// Where a value of the effective configuration comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// Built-in default
+    Default,
+    /// Configuration file, either the loaded one or one it extends
+    File(PathBuf),
+    /// Built-in preset extended by a configuration file
+    Preset(String),
+    /// `FLC_*` environment variable
+    Environment,
+    /// Command-line argument
+    CommandLine,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Preset(name) => write!(f, "{}{}", PRESET_PREFIX, name),
+            Self::Environment => write!(f, "environment"),
+            Self::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The effective configuration with the source of each top-level key
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    /// Fully merged and validated configuration
+    pub config: Config,
+    /// Configuration file that was loaded, if any
+    pub file: Option<PathBuf>,
+    /// Source of each key set in `config` (unset optional keys are absent)
+    pub sources: BTreeMap<String, ValueSource>,
 }
 
 /// Load configuration with the following priority (highest to lowest):
@@ -29,63 +109,457 @@ pub struct CliOverrides {
 /// 2. Environment variables
 /// 3. Configuration file
 /// 4. Default values
+///
+/// Without a `config_path`, the configuration file is discovered upwards from
+/// the current directory.
 #[tracing::instrument(skip(cli_overrides))]
 pub fn load_config(config_path: Option<&Path>, cli_overrides: CliOverrides) -> Result<Config> {
-    // Start with defaults
-    let mut config = Config::default();
+    load_config_from(Path::new("."), config_path, cli_overrides)
+}
+
+/// Load configuration for a scan root, discovering the configuration file
+/// upwards from `root` when `config_path` is not given
+#[tracing::instrument(skip(cli_overrides))]
+pub fn load_config_from(
+    root: &Path,
+    config_path: Option<&Path>,
+    cli_overrides: CliOverrides,
+) -> Result<Config> {
+    Ok(load_effective_config(root, config_path, cli_overrides)?.config)
+}
 
-    // Load from configuration file if it exists
-    if let Some(path) = config_path {
-        if path.exists() {
-            config = load_from_file(path)?;
+/// Load configuration like [`load_config_from`], recording where each value comes from
+///
+/// An explicit `config_path` must exist.
+#[tracing::instrument(skip(cli_overrides))]
+pub fn load_effective_config(
+    root: &Path,
+    config_path: Option<&Path>,
+    cli_overrides: CliOverrides,
+) -> Result<EffectiveConfig> {
+    // Use the explicit configuration file, or the nearest one above the root
+    let file = match config_path {
+        Some(path) if !path.exists() => {
+            return Err(ConfigError::NotFound(path.to_path_buf()).into());
         }
-    } else {
-        // Try default config file locations
-        let default_paths = [
-            PathBuf::from(".flc.toml"),
-            PathBuf::from(".flc.json"),
-            PathBuf::from("flc.toml"),
-            PathBuf::from("flc.json"),
-        ];
-
-        for path in &default_paths {
-            if path.exists() {
-                config = load_from_file(path)?;
-                break;
-            }
+        Some(path) => Some(path.to_path_buf()),
+        None => discover_config(root),
+    };
+
+    // Start with defaults, then the configuration file and what it extends
+    let (file_config, file_sources) = match &file {
+        Some(path) => {
+            tracing::debug!(path = %path.display(), "Loading configuration file");
+            (load_from_file(path)?, read_config_sources(path)?.1)
         }
-    }
+        None => (Config::default(), BTreeMap::new()),
+    };
 
     // Apply environment variable overrides
-    config = apply_env_overrides(config)?;
+    let env_config = apply_env_overrides(file_config.clone())?;
 
     // Apply CLI overrides
-    config = apply_cli_overrides(config, cli_overrides)?;
+    let config = apply_cli_overrides(env_config.clone(), cli_overrides)?;
 
     // Validate final configuration
     validate_config(&config)?;
 
-    Ok(config)
+    // Attribute each key to the last layer that changed it
+    let file_table = config_table(&file_config)?;
+    let env_table = config_table(&env_config)?;
+    let final_table = config_table(&config)?;
+    let sources = final_table
+        .keys()
+        .map(|key| {
+            let source = if final_table.get(key) != env_table.get(key) {
+                ValueSource::CommandLine
+            } else if env_table.get(key) != file_table.get(key) {
+                ValueSource::Environment
+            } else {
+                file_sources.get(key).cloned().unwrap_or(ValueSource::Default)
+            };
+            (key.clone(), source)
+        })
+        .collect();
+
+    Ok(EffectiveConfig { config, file, sources })
 }
 
-/// Load configuration from a TOML or JSON file
-#[tracing::instrument]
-fn load_from_file(path: &Path) -> Result<Config> {
+/// Serialize a configuration into a TOML table
+fn config_table(config: &Config) -> Result<toml::Table> {
+    Ok(toml::Table::try_from(config).map_err(|e| ConfigError::InvalidValue {
+        field: "config",
+        message: format!("Could not serialize configuration: {}", e),
+    })?)
+}
+
+/// Find the root configuration file in `start` or its nearest ancestor
+///
+/// In each directory, dedicated configuration files take precedence over project
+/// manifests with an `flc` section; a closer directory always wins.
+pub fn discover_config(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    let dir = if start.is_file() { start.parent()? } else { start.as_path() };
+
+    dir.ancestors().find_map(|ancestor| {
+        CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| ancestor.join(name))
+            .find(|candidate| candidate.is_file())
+            .or_else(|| {
+                MANIFEST_FILE_NAMES.iter().map(|name| ancestor.join(name)).find(|candidate| {
+                    candidate.is_file() && matches!(read_manifest_config(candidate), Ok(Some(_)))
+                })
+            })
+    })
+}
+
+/// Read the configuration embedded in a project manifest, if it has an `flc` section
+pub fn read_manifest_config(path: &Path) -> Result<Option<toml::Table>> {
     let content =
         fs::read_to_string(path).map_err(|_| ConfigError::NotFound(path.to_path_buf()))?;
+    parse_manifest(path, &content)
+}
 
-    let config = if path.extension().and_then(|s| s.to_str()) == Some("json") {
-        serde_json::from_str(&content).map_err(|e| ConfigError::InvalidValue {
-            field: "config_file",
-            message: format!("Invalid JSON format: {}", e),
-        })
+/// Whether `path` names a project manifest rather than a dedicated config file
+fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| MANIFEST_FILE_NAMES.contains(&name))
+}
+
+/// Extract the `flc` section of a project manifest
+fn parse_manifest(path: &Path, content: &str) -> Result<Option<toml::Table>> {
+    let keys: &[&[&str]] = match path.file_name().and_then(|name| name.to_str()) {
+        Some("Cargo.toml") => &[&["package", "metadata", "flc"], &["workspace", "metadata", "flc"]],
+        Some("pyproject.toml") => &[&["tool", "flc"]],
+        Some("package.json") => {
+            // package.json may hold nulls that a TOML table can't represent
+            let manifest: serde_json::Value =
+                serde_json::from_str(content).map_err(|e| json_error(path, &e))?;
+            let Some(section) = manifest.get("flc") else {
+                return Ok(None);
+            };
+            let table = serde_json::from_value(section.clone()).map_err(|e| {
+                located_error(path, content, &e.to_string(), find_key(content, "flc"))
+            })?;
+            return Ok(Some(table));
+        }
+        _ => return Ok(None),
+    };
+
+    let manifest: toml::Table =
+        toml::from_str(content).map_err(|e| toml_error(path, content, e))?;
+    Ok(keys.iter().find_map(|keys| manifest_section(&manifest, keys)))
+}
+
+/// Look up a nested table such as `tool.flc`
+fn manifest_section(manifest: &toml::Table, keys: &[&str]) -> Option<toml::Table> {
+    let (last, parents) = keys.split_last()?;
+    let mut current = manifest;
+    for key in parents {
+        current = current.get(*key)?.as_table()?;
+    }
+    current.get(*last)?.as_table().cloned()
+}
+
+/// Read a (possibly partial) configuration file as a table, with its
+/// `extends` chain resolved
+pub fn read_config_table(path: &Path) -> Result<toml::Table> {
+    resolve_file(path, &mut Vec::new(), &mut BTreeMap::new())
+}
+
+/// Read a configuration file like [`read_config_table`], also returning the layer
+/// (the file itself or one it extends) that last set each top-level key
+fn read_config_sources(path: &Path) -> Result<(toml::Table, BTreeMap<String, ValueSource>)> {
+    let mut sources = BTreeMap::new();
+    let table = resolve_file(path, &mut Vec::new(), &mut sources)?;
+    Ok((table, sources))
+}
+
+/// Merge `overlay` into `base`
+///
+/// This is the one rule by which configuration layers combine: a file onto the
+/// defaults, `extends` layers onto each other and nested `.flc.toml` files onto
+/// their parent directory's configuration. Tables merge entry by entry (an entry
+/// replaces the inherited entry as a whole), lists are appended without
+/// duplicates, and any other value replaces the base.
+pub fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(entries)) => {
+                existing.extend(entries);
+            }
+            (Some(toml::Value::Array(existing)), toml::Value::Array(items)) => {
+                for item in items {
+                    if !existing.contains(&item) {
+                        existing.push(item);
+                    }
+                }
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Parse TOML or JSON configuration content (or a manifest's `flc` section) into a table
+fn parse_config_table(path: &Path, content: &str) -> Result<toml::Table> {
+    if is_manifest(path) {
+        Ok(parse_manifest(path, content)?.unwrap_or_default())
+    } else if is_json(path) {
+        Ok(serde_json::from_str(content).map_err(|e| json_error(path, &e))?)
     } else {
-        toml::from_str(&content).map_err(ConfigError::Parse)
-    }?;
+        Ok(toml::from_str(content).map_err(|e| toml_error(path, content, e))?)
+    }
+}
+
+/// Whether `path` is a JSON configuration file
+fn is_json(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("json")
+}
 
+/// Check a configuration file's own keys and value types against [`Config`]
+///
+/// Dedicated files are deserialized from their raw content so that errors carry the
+/// line and column of the offending key; embedded manifest sections are located by key.
+fn check_fields(path: &Path, content: &str, table: &toml::Table) -> Result<()> {
+    if is_manifest(path) {
+        toml::Value::Table(table.clone())
+            .try_into::<Config>()
+            .map_err(|e| located_error(path, content, e.message(), None))?;
+    } else if is_json(path) {
+        serde_json::from_str::<Config>(content).map_err(|e| json_error(path, &e))?;
+    } else {
+        toml::from_str::<Config>(content).map_err(|e| toml_error(path, content, e))?;
+    }
+    Ok(())
+}
+
+/// Convert a TOML error into a configuration error with its location
+fn toml_error(path: &Path, content: &str, error: toml::de::Error) -> ConfigError {
+    let location = error.span().map(|span| line_column(content, span.start));
+    located_error(path, content, error.message().trim(), location)
+}
+
+/// Convert a JSON error into a configuration error with its location
+fn json_error(path: &Path, error: &serde_json::Error) -> ConfigError {
+    // serde_json appends " at line L column C" to the message
+    let message = error.to_string();
+    let message = message.rsplit_once(" at line ").map_or(message.as_str(), |(message, _)| message);
+    let location = (error.line() > 0).then(|| (error.line(), error.column()));
+    located_error(path, "", message, location)
+}
+
+/// Build an unknown-field error with a suggestion, or a syntax error
+///
+/// Unknown keys without a parser location are searched for in `content`.
+fn located_error(
+    path: &Path,
+    content: &str,
+    message: &str,
+    location: Option<(usize, usize)>,
+) -> ConfigError {
+    let at =
+        |(line, column): (usize, usize)| ConfigLocation { path: path.to_path_buf(), line, column };
+
+    if let Some((field, expected)) = unknown_field(message) {
+        let suggestion = expected
+            .iter()
+            .map(|candidate| (edit_distance(field, candidate), *candidate))
+            .filter(|(distance, _)| *distance <= field.chars().count().div_ceil(3).max(2))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, candidate)| candidate.to_string());
+        return ConfigError::UnknownField {
+            field: field.to_string(),
+            location: at(location.or_else(|| find_key(content, field)).unwrap_or((0, 0))),
+            suggestion,
+        };
+    }
+
+    ConfigError::Syntax { location: at(location.unwrap_or((0, 0))), message: message.to_string() }
+}
+
+/// Split serde's "unknown field `x`, expected one of `a`, `b`" into the field and candidates
+fn unknown_field(message: &str) -> Option<(&str, Vec<&str>)> {
+    let (field, expected) = message.strip_prefix("unknown field `")?.split_once('`')?;
+    Some((field, expected.split('`').skip(1).step_by(2).collect()))
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = content.get(..offset).unwrap_or(content);
+    let line = before.matches('\n').count().saturating_add(1);
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count().saturating_add(1);
+    (line, column)
+}
+
+/// Find the first place `key` is used as a key (`key =`, `"key":`, `[key]`, `key.x`)
+fn find_key(content: &str, key: &str) -> Option<(usize, usize)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    content.lines().enumerate().find_map(|(index, line)| {
+        line.match_indices(key).find_map(|(start, _)| {
+            let before = line.get(..start)?;
+            let after = line.get(start.saturating_add(key.len())..)?;
+            let starts_word = !before.chars().next_back().is_some_and(is_word);
+            let after = after.trim_start_matches(['"', '\'']).trim_start();
+            let used_as_key = after.starts_with(['=', ':', ']', '.']);
+            (starts_word && used_as_key)
+                .then(|| (index.saturating_add(1), before.chars().count().saturating_add(1)))
+        })
+    })
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = Vec::with_capacity(previous.len());
+        current.push(i.saturating_add(1));
+        for (j, cb) in b.iter().enumerate() {
+            let substitution =
+                previous.get(j).copied().unwrap_or_default().saturating_add(usize::from(ca != *cb));
+            let deletion =
+                previous.get(j.saturating_add(1)).copied().unwrap_or_default().saturating_add(1);
+            let insertion = current.last().copied().unwrap_or_default().saturating_add(1);
+            current.push(substitution.min(deletion).min(insertion));
+        }
+        previous = current;
+    }
+    previous.last().copied().unwrap_or_default()
+}
+
+/// Read a configuration file and resolve what it extends
+fn resolve_file(
+    path: &Path,
+    stack: &mut Vec<String>,
+    sources: &mut BTreeMap<String, ValueSource>,
+) -> Result<toml::Table> {
+    let content =
+        fs::read_to_string(path).map_err(|_| ConfigError::NotFound(path.to_path_buf()))?;
+    let table = parse_config_table(path, &content)?;
+    check_fields(path, &content, &table)?;
+
+    let id = path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).display().to_string();
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    resolve_table(id, ValueSource::File(path.to_path_buf()), table, base_dir, stack, sources)
+}
+
+/// Resolve the `extends` entries of a table, in order, below the table itself
+///
+/// `stack` holds the files and presets currently being resolved, for cycle detection.
+/// Each key is attributed in `sources` to the last layer that sets it, `source` being
+/// this table's.
+fn resolve_table(
+    id: String,
+    source: ValueSource,
+    mut table: toml::Table,
+    base_dir: &Path,
+    stack: &mut Vec<String>,
+    sources: &mut BTreeMap<String, ValueSource>,
+) -> Result<toml::Table> {
+    if let Some(start) = stack.iter().position(|entry| entry == &id) {
+        let cycle: Vec<&str> = stack.iter().skip(start).map(String::as_str).collect();
+        return Err(ConfigError::InvalidValue {
+            field: "extends",
+            message: format!("cycle detected: {} -> {}", cycle.join(" -> "), id),
+        }
+        .into());
+    }
+
+    let extends = match table.remove("extends") {
+        None => Vec::new(),
+        Some(toml::Value::String(entry)) => vec![entry],
+        Some(toml::Value::Array(entries)) => entries
+            .into_iter()
+            .map(|entry| match entry {
+                toml::Value::String(entry) => Ok(entry),
+                other => Err(ConfigError::InvalidValue {
+                    field: "extends",
+                    message: format!("{}: expected a path or preset name, found {}", id, other),
+                }),
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+        Some(other) => {
+            return Err(ConfigError::InvalidValue {
+                field: "extends",
+                message: format!("{}: expected a list of paths or presets, found {}", id, other),
+            }
+            .into());
+        }
+    };
+
+    stack.push(id.clone());
+    let mut merged = toml::Table::new();
+    for entry in extends {
+        let parent = if let Some(name) = entry.strip_prefix(PRESET_PREFIX) {
+            let source = preset(name).ok_or_else(|| ConfigError::InvalidValue {
+                field: "extends",
+                message: format!(
+                    "{}: unknown preset '{}' (available: {})",
+                    id,
+                    name,
+                    preset_names().collect::<Vec<_>>().join(", ")
+                ),
+            })?;
+            let preset_table = toml::from_str(source).map_err(ConfigError::Parse)?;
+            resolve_table(
+                format!("{}{}", PRESET_PREFIX, name),
+                ValueSource::Preset(name.to_string()),
+                preset_table,
+                base_dir,
+                stack,
+                sources,
+            )?
+        } else {
+            let parent_path = base_dir.join(&entry);
+            if !parent_path.is_file() {
+                return Err(ConfigError::InvalidValue {
+                    field: "extends",
+                    message: format!(
+                        "{}: extended file '{}' not found at {}",
+                        id,
+                        entry,
+                        parent_path.display()
+                    ),
+                }
+                .into());
+            }
+            resolve_file(&parent_path, stack, sources)?
+        };
+        merge_tables(&mut merged, parent);
+    }
+    stack.pop();
+
+    sources.extend(table.keys().map(|key| (key.clone(), source.clone())));
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+/// Apply a partial configuration table on top of `base`, as [`merge_tables`] does
+pub fn overlay_config(base: &Config, overlay: toml::Table) -> Result<Config> {
+    let mut merged = config_table(base)?;
+    merge_tables(&mut merged, overlay);
+
+    let config: Config = toml::Value::Table(merged).try_into().map_err(ConfigError::Parse)?;
+    validate_config(&config)?;
     Ok(config)
 }
 
+/// Load configuration from a TOML or JSON file, or a project manifest's `flc` section
+///
+/// The file, with its `extends` chain resolved, is merged onto the built-in
+/// defaults, so its comment styles add to the default tables instead of replacing them.
+#[tracing::instrument]
+fn load_from_file(path: &Path) -> Result<Config> {
+    let mut table = config_table(&Config::default())?;
+    merge_tables(&mut table, read_config_table(path)?);
+
+    Ok(toml::Value::Table(table).try_into().map_err(ConfigError::Parse)?)
+}
+
 /// Apply environment variable overrides
 #[tracing::instrument]
 fn apply_env_overrides(mut config: Config) -> Result<Config> {
@@ -152,6 +626,50 @@ fn apply_cli_overrides(mut config: Config, cli_overrides: CliOverrides) -> Resul
         config.similarity_threshold = threshold.min(100);
     }
 
+    if let Some(bytes) = cli_overrides.max_file_size {
+        config.max_file_size = Some(bytes);
+    }
+
+    if let Some(hidden) = cli_overrides.include_hidden {
+        config.include_hidden = hidden;
+    }
+
+    if let Some(follow) = cli_overrides.follow_symlinks {
+        config.follow_symlinks = follow;
+    }
+
+    if let Some(depth) = cli_overrides.max_depth {
+        config.max_depth = Some(depth);
+    }
+
+    if let Some(respect) = cli_overrides.respect_gitignore {
+        config.respect_gitignore = respect;
+    }
+
+    if let Some(name) = cli_overrides.ignore_filename {
+        config.ignore_filename = name;
+    }
+
+    config.include_patterns.extend(cli_overrides.include_patterns);
+    config.only_extensions.extend(cli_overrides.only_extensions);
+    config.only_languages.extend(cli_overrides.only_languages);
+
+    if let Some(fail) = cli_overrides.fail_on_unreadable {
+        config.fail_on_unreadable = fail;
+    }
+
+    if let Some(require) = cli_overrides.require_suppression_reason {
+        config.require_suppression_reason = require;
+    }
+
+    if let Some(check) = cli_overrides.check_packages {
+        config.check_packages = check;
+    }
+
+    if let Some(git_history) = cli_overrides.git_history {
+        config.copyright.git_history = git_history;
+    }
+
     Ok(config)
 }
 
@@ -184,6 +702,14 @@ fn validate_config(config: &Config) -> Result<()> {
         }
     }
 
+    // Validate max_file_size is non-zero if set
+    if config.max_file_size == Some(0) {
+        return Err(crate::error::LicenseCheckerError::Config(ConfigError::InvalidValue {
+            field: "max_file_size",
+            message: "must be greater than 0".to_string(),
+        }));
+    }
+
     Ok(())
 }
 
@@ -203,6 +729,19 @@ pub fn create_config_template(path: &Path, format: &str) -> Result<()> {
             template.push_str("# rs = { prefix = \"//\" }\n");
             template.push_str("# py = { prefix = \"#\" }\n");
             template.push_str("# css = { prefix = \"/*\", suffix = \"*/\" }\n");
+            template.push_str(
+                "# java = { opening = \"/*\", line_prefix = \" *\", closing = \" */\" }\n",
+            );
+            template.push('\n');
+            template.push_str("# Comment styles per exact file name and per glob\n");
+            template.push_str("# Precedence: exact name, then longest glob, then extension\n");
+            template.push_str("# [filename_comment_styles]\n");
+            template.push_str("# Jenkinsfile = { prefix = \"//\" }\n");
+            template.push_str("# [glob_comment_styles]\n");
+            template.push_str("# \"*.d.ts\" = { prefix = \"//\" }\n");
+            template.push_str("# Fallback for extensionless scripts (shebang or modeline)\n");
+            template.push_str("# [interpreter_comment_styles]\n");
+            template.push_str("# python = { prefix = \"#\" }\n");
             template.push('\n');
             template.push_str("# Additional ignore patterns (beyond .gitignore)\n");
             template.push_str("ignore_patterns = [\n");
@@ -283,6 +822,319 @@ mod tests {
         assert_eq!(config.similarity_threshold, 80);
     }
 
+    #[test]
+    fn load_config_rejects_zero_max_file_size() {
+        let overrides = CliOverrides {
+            license_header: Some("Test License".to_string()),
+            max_file_size: Some(0),
+            ..Default::default()
+        };
+        assert!(load_config(None, overrides).is_err());
+
+        let overrides = CliOverrides {
+            license_header: Some("Test License".to_string()),
+            max_file_size: Some(1024),
+            ..Default::default()
+        };
+        assert_eq!(load_config(None, overrides).unwrap().max_file_size, Some(1024));
+    }
+
+    #[test]
+    fn discover_config_searches_ancestors() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a/b");
+        fs::create_dir_all(&nested).unwrap();
+        assert!(discover_config(&nested).map_or(true, |p| !p.starts_with(temp_dir.path())));
+
+        fs::write(temp_dir.path().join(".flc.toml"), "license_header = \"Root\"\n").unwrap();
+        let found = discover_config(&nested).unwrap();
+        assert_eq!(found.file_name().unwrap(), ".flc.toml");
+
+        let overrides = CliOverrides::default();
+        let config = load_config_from(&nested, None, overrides).unwrap();
+        assert_eq!(config.license_header, "Root");
+    }
+
+    #[test]
+    fn discover_config_reads_manifest_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        // A manifest without an flc section is not a configuration
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        assert!(discover_config(root).map_or(true, |p| !p.starts_with(root)));
+
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n\n[package.metadata.flc]\nlicense_header = \"Cargo\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{"name": "demo", "main": null, "flc": {"license_header": "Npm"}}"#,
+        )
+        .unwrap();
+        let config = load_config_from(root, None, CliOverrides::default()).unwrap();
+        assert_eq!(config.license_header, "Cargo");
+        assert!(config.comment_styles.contains_key("rs"));
+
+        // Dedicated files win over manifests in the same directory
+        fs::write(root.join(".flc.toml"), "license_header = \"Dotfile\"\n").unwrap();
+        let config = load_config_from(root, None, CliOverrides::default()).unwrap();
+        assert_eq!(config.license_header, "Dotfile");
+    }
+
+    #[test]
+    fn parse_manifest_sections() {
+        let workspace =
+            "[workspace]\nmembers = []\n[workspace.metadata.flc]\nmax_header_bytes = 1024\n";
+        let table = parse_manifest(Path::new("Cargo.toml"), workspace).unwrap().unwrap();
+        assert_eq!(table.get("max_header_bytes").and_then(toml::Value::as_integer), Some(1024));
+
+        let pyproject = "[project]\nname = \"demo\"\n[tool.flc]\nlicense_header = \"Py\"\n";
+        let table = parse_manifest(Path::new("pyproject.toml"), pyproject).unwrap().unwrap();
+        assert_eq!(table.get("license_header").and_then(toml::Value::as_str), Some("Py"));
+
+        let package = r#"{"name": "demo"}"#;
+        assert!(parse_manifest(Path::new("package.json"), package).unwrap().is_none());
+    }
+
+    #[test]
+    fn load_effective_config_records_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+        fs::write(&config_path, "license_header = \"File\"\nmax_header_bytes = 4096\n").unwrap();
+
+        let overrides = CliOverrides { max_file_size: Some(1024), ..Default::default() };
+        let effective = load_effective_config(temp_dir.path(), None, overrides).unwrap();
+        assert_eq!(effective.file.as_deref(), Some(config_path.as_path()));
+        assert_eq!(effective.sources["license_header"], ValueSource::File(config_path.clone()));
+        assert_eq!(effective.sources["max_file_size"], ValueSource::CommandLine);
+        assert_eq!(effective.sources["skip_empty_files"], ValueSource::Default);
+        assert!(!effective.sources.contains_key("max_depth"));
+    }
+
+    #[test]
+    fn load_effective_config_attributes_extended_layers() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("base.toml");
+        fs::write(&base_path, "ignore_patterns = [\"target/\"]\nmax_header_bytes = 2048\n")
+            .unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+        fs::write(
+            &config_path,
+            "extends = [\"base.toml\", \"preset:mit\"]\nmax_header_bytes = 4096\n",
+        )
+        .unwrap();
+
+        let effective =
+            load_effective_config(temp_dir.path(), None, CliOverrides::default()).unwrap();
+        assert_eq!(effective.sources["ignore_patterns"], ValueSource::File(base_path));
+        assert_eq!(effective.sources["license_header"], ValueSource::Preset("mit".to_string()));
+        assert_eq!(effective.sources["license_header"].to_string(), "preset:mit");
+        assert_eq!(effective.sources["max_header_bytes"], ValueSource::File(config_path));
+        assert_eq!(effective.sources["skip_empty_files"], ValueSource::Default);
+    }
+
+    #[test]
+    fn load_effective_config_requires_explicit_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing.toml");
+        let result =
+            load_effective_config(temp_dir.path(), Some(&missing), CliOverrides::default());
+        assert!(result.unwrap_err().to_string().contains("Config file not found"));
+    }
+
+    #[test]
+    fn overlay_config_merges_comment_styles() {
+        let base = Config::default().with_license_header("Base License");
+        let overlay: toml::Table = toml::from_str(
+            r##"
+            license_header = "Vendored License"
+            ignore_patterns = ["*.gen.rs"]
+            [comment_styles]
+            rs = { prefix = "#" }
+            "##,
+        )
+        .unwrap();
+
+        let config = overlay_config(&base, overlay).unwrap();
+        assert_eq!(config.license_header, "Vendored License");
+        assert_eq!(config.ignore_patterns, vec!["*.gen.rs"]);
+        assert_eq!(config.comment_styles.get("rs").unwrap().prefix, "#");
+        // Untouched entries and fields are inherited
+        assert_eq!(config.comment_styles.get("py").unwrap().prefix, "#");
+        assert_eq!(config.max_header_bytes, base.max_header_bytes);
+    }
+
+    #[test]
+    fn files_and_nested_overlays_merge_like_extends() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+        fs::write(
+            &config_path,
+            "license_header = \"File\"\nignore_patterns = [\"target/\"]\n\
+             [comment_styles]\nfoo = { prefix = \"--\" }\n",
+        )
+        .unwrap();
+
+        // Without `extends`, the file's comment styles still add to the defaults
+        let config = load_from_file(&config_path).unwrap();
+        assert_eq!(config.comment_styles.get("foo").unwrap().prefix, "--");
+        assert_eq!(config.comment_styles.get("rs").unwrap().prefix, "//");
+
+        // A nested overlay appends to inherited lists
+        let overlay: toml::Table =
+            toml::from_str("ignore_patterns = [\"*.gen.rs\", \"target/\"]").unwrap();
+        let nested = overlay_config(&config, overlay).unwrap();
+        assert_eq!(nested.ignore_patterns, vec!["target/", "*.gen.rs"]);
+        assert_eq!(nested.comment_styles.get("foo").unwrap().prefix, "--");
+    }
+
+    #[test]
+    fn overlay_config_validates_result() {
+        let base = Config::default();
+        let overlay: toml::Table = toml::from_str("max_header_bytes = 1").unwrap();
+        assert!(overlay_config(&base, overlay).is_err());
+    }
+
+    #[test]
+    fn extends_files_and_presets_in_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared");
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir_all(&shared).unwrap();
+        fs::create_dir_all(&repo).unwrap();
+
+        fs::write(
+            shared.join("flc-base.toml"),
+            "ignore_patterns = [\"vendor/\"]\nmax_header_bytes = 4096\n\
+             [comment_styles]\nrs = { prefix = \"/*\", suffix = \"*/\" }\n",
+        )
+        .unwrap();
+        let config_path = repo.join(".flc.toml");
+        fs::write(
+            &config_path,
+            "extends = [\"../shared/flc-base.toml\", \"preset:apache-2.0\"]\n\
+             ignore_patterns = [\"target/\", \"vendor/\"]\n\
+             [comment_styles]\nrs = { prefix = \"//\" }\n",
+        )
+        .unwrap();
+
+        let config = load_from_file(&config_path).unwrap();
+        assert!(config.license_header.contains("Apache License, Version 2.0"));
+        assert_eq!(config.max_header_bytes, 4096);
+        // Lists append without duplicates, base first
+        assert_eq!(config.ignore_patterns, vec!["vendor/", "target/"]);
+        // Map entries replace whole entries; defaults are kept
+        let rs = config.comment_styles.get("rs").unwrap();
+        assert_eq!(rs.prefix, "//");
+        assert!(rs.suffix.is_none());
+        assert!(config.comment_styles.contains_key("py"));
+    }
+
+    #[test]
+    fn extends_detects_cycles() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.toml");
+        let b = temp_dir.path().join("b.toml");
+        fs::write(&a, "extends = [\"b.toml\"]\n").unwrap();
+        fs::write(&b, "extends = [\"a.toml\"]\n").unwrap();
+
+        let message = load_from_file(&a).unwrap_err().to_string();
+        assert!(message.contains("cycle detected"), "{}", message);
+        assert!(message.contains("a.toml -> "), "{}", message);
+    }
+
+    #[test]
+    fn extends_reports_missing_files_and_presets() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+
+        fs::write(&config_path, "extends = [\"missing.toml\"]\n").unwrap();
+        let message = load_from_file(&config_path).unwrap_err().to_string();
+        assert!(message.contains("extended file 'missing.toml' not found"), "{}", message);
+
+        fs::write(&config_path, "extends = [\"preset:nope\"]\n").unwrap();
+        let message = load_from_file(&config_path).unwrap_err().to_string();
+        assert!(message.contains("unknown preset 'nope'"), "{}", message);
+        assert!(message.contains("apache-2.0"), "{}", message);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_with_suggestion() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+        fs::write(&config_path, "license_header = \"X\"\nignore_pattern = [\"vendor/\"]\n")
+            .unwrap();
+
+        let error = load_from_file(&config_path).unwrap_err();
+        let crate::error::LicenseCheckerError::Config(ConfigError::UnknownField {
+            field,
+            location,
+            suggestion,
+        }) = error
+        else {
+            unreachable!("expected an unknown field error, got {error}")
+        };
+        assert_eq!(field, "ignore_pattern");
+        assert_eq!((location.line, location.column), (2, 1));
+        assert_eq!(suggestion.as_deref(), Some("ignore_patterns"));
+    }
+
+    #[test]
+    fn unknown_fields_in_comment_styles_and_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".flc.toml");
+
+        fs::write(&config_path, "[comment_style]\nrs = { prefix = \"//\" }\n").unwrap();
+        let message = load_from_file(&config_path).unwrap_err().to_string();
+        assert!(message.contains(":1:"), "{}", message);
+        assert!(message.contains("did you mean `comment_styles`?"), "{}", message);
+
+        fs::write(&config_path, "[comment_styles]\nrs = { prefx = \"//\" }\n").unwrap();
+        let message = load_from_file(&config_path).unwrap_err().to_string();
+        assert!(message.contains("did you mean `prefix`?"), "{}", message);
+
+        fs::write(&config_path, "frobnicate = true\n").unwrap();
+        let message = load_from_file(&config_path).unwrap_err().to_string();
+        assert!(message.contains("Unknown field `frobnicate`"), "{}", message);
+        assert!(!message.contains("did you mean"), "{}", message);
+    }
+
+    #[test]
+    fn json_and_manifest_errors_have_locations() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let json_path = temp_dir.path().join(".flc.json");
+        fs::write(&json_path, "{\n  \"max_header_bytes\": \"big\"\n}").unwrap();
+        let message = load_from_file(&json_path).unwrap_err().to_string();
+        assert!(message.contains(".flc.json:2:"), "{}", message);
+        assert!(!message.contains("at line"), "{}", message);
+
+        fs::write(&json_path, "{\n  \"skip_empty_file\": true\n}").unwrap();
+        let message = load_from_file(&json_path).unwrap_err().to_string();
+        assert!(message.contains("did you mean `skip_empty_files`?"), "{}", message);
+
+        let cargo_path = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_path,
+            "[package]\nname = \"demo\"\n\n[package.metadata.flc]\nmax_depht = 2\n",
+        )
+        .unwrap();
+        let message = load_from_file(&cargo_path).unwrap_err().to_string();
+        assert!(message.contains("Cargo.toml:5:1"), "{}", message);
+        assert!(message.contains("did you mean `max_depth`?"), "{}", message);
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance("ignore_pattern", "ignore_patterns"), 1);
+        assert_eq!(edit_distance("prefx", "prefix"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
     #[test]
     fn load_config_with_license_file() {
         let temp_dir = TempDir::new().unwrap();
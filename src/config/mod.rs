@@ -57,8 +57,17 @@
 //! ```
 
 pub mod loader;
+pub mod presets;
+pub mod schema;
 pub mod types;
 
 // Re-export main types and functions for convenience
-pub use loader::{create_config_template, load_config, CliOverrides};
-pub use types::{CommentStyleConfig, Config};
+pub use loader::{
+    create_config_template, discover_config, load_config, load_config_from, load_effective_config,
+    overlay_config, read_manifest_config, CliOverrides, EffectiveConfig, ValueSource,
+    MANIFEST_FILE_NAMES, NESTED_CONFIG_FILE,
+};
+pub use schema::config_schema;
+pub use types::{
+    CommentStyleConfig, Config, CopyrightConfig, PolicyConfig, PolicyPathRule, ThirdPartyRule,
+};
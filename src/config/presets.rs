@@ -0,0 +1,51 @@
+//! Built-in configuration presets.
+//!
+//! Presets are partial configuration files compiled into the binary and
+//! referenced from `extends` as `preset:<name>`.
+
+/// Prefix marking an `extends` entry as a built-in preset
+pub const PRESET_PREFIX: &str = "preset:";
+
+/// Names and TOML sources of all built-in presets
+const PRESETS: [(&str, &str); 6] = [
+    ("apache-2.0", include_str!("presets/apache-2.0.toml")),
+    ("bsd-3-clause", include_str!("presets/bsd-3-clause.toml")),
+    ("gpl-3.0-or-later", include_str!("presets/gpl-3.0-or-later.toml")),
+    ("javadoc", include_str!("presets/javadoc.toml")),
+    ("mit", include_str!("presets/mit.toml")),
+    ("mpl-2.0", include_str!("presets/mpl-2.0.toml")),
+];
+
+/// Returns the TOML source of a built-in preset (names are case-insensitive)
+pub fn preset(name: &str) -> Option<&'static str> {
+    PRESETS
+        .iter()
+        .find(|(preset_name, _)| preset_name.eq_ignore_ascii_case(name))
+        .map(|(_, source)| *source)
+}
+
+/// Names of all built-in presets
+pub fn preset_names() -> impl Iterator<Item = &'static str> {
+    PRESETS.iter().map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn presets_parse_as_config() {
+        for name in preset_names() {
+            let source = preset(name).unwrap();
+            assert!(toml::from_str::<Config>(source).is_ok(), "preset {} is invalid", name);
+        }
+    }
+
+    #[test]
+    fn preset_lookup_is_case_insensitive() {
+        assert!(preset("Apache-2.0").unwrap().contains("Apache License"));
+        assert!(preset("unknown").is_none());
+    }
+}
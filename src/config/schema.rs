@@ -0,0 +1,68 @@
+//! JSON Schema for configuration files.
+//!
+//! Generated from [`Config`] so editors can offer completion and validation
+//! for `.flc.toml` and `.flc.json`.
+
+use serde_json::Value;
+
+use crate::config::presets::{preset_names, PRESET_PREFIX};
+use crate::config::types::Config;
+
+/// Returns the JSON Schema describing a configuration file
+///
+/// `extends` is documented with the names of the built-in presets.
+pub fn config_schema() -> Value {
+    let mut schema = schemars::schema_for!(Config).to_value();
+
+    let presets: Vec<Value> =
+        preset_names().map(|name| Value::from(format!("{}{}", PRESET_PREFIX, name))).collect();
+    let extends = object([
+        (
+            "description",
+            Value::from(
+                "Configuration files (relative to this file) and built-in presets merged \
+                 below this file, in order",
+            ),
+        ),
+        ("type", Value::from("array")),
+        (
+            "items",
+            object([(
+                "anyOf",
+                Value::Array(vec![
+                    object([("enum", Value::Array(presets))]),
+                    object([("type", Value::from("string"))]),
+                ]),
+            )]),
+        ),
+        ("default", Value::Array(Vec::new())),
+    ]);
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        properties.insert("extends".to_string(), extends);
+    }
+
+    schema
+}
+
+/// Build a JSON object from key/value pairs
+fn object<const N: usize>(entries: [(&str, Value); N]) -> Value {
+    Value::Object(entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_describes_config_fields() {
+        let schema = config_schema();
+        let properties = schema.get("properties").and_then(Value::as_object).unwrap();
+
+        for field in ["license_header", "comment_styles", "ignore_patterns", "extends"] {
+            assert!(properties.contains_key(field), "missing {}", field);
+        }
+        let extends = serde_json::to_string(&properties["extends"]).unwrap();
+        assert!(extends.contains("preset:apache-2.0"));
+    }
+}
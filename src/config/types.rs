@@ -3,28 +3,103 @@
 //! Defines the main configuration structure with default values
 //! and support for various file types and comment styles.
 
+// The `JsonSchema` derive serializes default values with `unwrap`
+#![allow(clippy::disallowed_methods)]
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::types::{CommentStyle, LicenseClassification};
 
 /// Main configuration for the license checker
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
+    /// Configuration files and `preset:` names this file extends, resolved while
+    /// loading (always empty in a loaded configuration)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+
     /// The license header text to check for
     pub license_header: String,
 
     /// Comment styles per file extension
     pub comment_styles: HashMap<String, CommentStyleConfig>,
 
+    /// Comment styles per exact file name (e.g., "Dockerfile"), checked first
+    pub filename_comment_styles: HashMap<String, CommentStyleConfig>,
+
+    /// Comment styles per glob (e.g., "*.d.ts"), longest matching glob wins
+    pub glob_comment_styles: HashMap<String, CommentStyleConfig>,
+
+    /// Comment styles per shebang interpreter or modeline language (e.g., "python3"),
+    /// used when no file name, glob or extension style matches
+    pub interpreter_comment_styles: HashMap<String, CommentStyleConfig>,
+
     /// Additional glob patterns to ignore (beyond .gitignore)
     pub ignore_patterns: Vec<String>,
 
+    /// Check hidden files and directories such as `.github/` (`.git/` is always skipped)
+    pub include_hidden: bool,
+
+    /// Follow symbolic links while walking (cycles are detected and skipped)
+    pub follow_symlinks: bool,
+
+    /// Maximum directory depth below the scan root (None = unlimited)
+    pub max_depth: Option<usize>,
+
+    /// Respect .gitignore, the global gitignore and .git/info/exclude
+    pub respect_gitignore: bool,
+
+    /// Name of gitignore-style files honoured in every directory (empty = disabled)
+    pub ignore_filename: String,
+
+    /// Gitignore-style globs relative to the scan root; when set, only matching
+    /// files are checked (e.g. "src/**/*.rs")
+    pub include_patterns: Vec<String>,
+
+    /// Only check files with these extensions (e.g. "rs", "py")
+    pub only_extensions: Vec<String>,
+
+    /// Only check files of these languages (e.g. "rust", "python")
+    pub only_languages: Vec<String>,
+
     /// Maximum bytes to read from file start for header check
     pub max_header_bytes: usize,
 
+    /// Skip files larger than this many bytes (None = no limit), e.g. generated
+    /// blobs and minified bundles
+    pub max_file_size: Option<u64>,
+
     /// Skip empty files (0 bytes)
     pub skip_empty_files: bool,
 
+    /// Count unreadable files (permission denied, I/O errors, broken symlinks) as
+    /// failures instead of skipping them
+    pub fail_on_unreadable: bool,
+
+    /// Only honour `flc:ignore-file` / `flc:disable` markers followed by a
+    /// justification; bare markers are ignored and the file is checked
+    pub require_suppression_reason: bool,
+
+    /// Check that every package (a directory with `Cargo.toml`, `package.json`,
+    /// `pyproject.toml`, `go.mod`, ...) has a LICENSE or COPYING file matching the
+    /// licenses in its file headers
+    pub check_packages: bool,
+
+    /// Vendored code whose existing license is classified instead of requiring our header
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub third_party: Vec<ThirdPartyRule>,
+
+    /// Allowed, denied and review-required licenses, globally and per path
+    #[serde(skip_serializing_if = "PolicyConfig::is_empty")]
+    pub policy: PolicyConfig,
+
+    /// Allowed copyright holders and canonical spellings of their names
+    #[serde(skip_serializing_if = "CopyrightConfig::is_empty")]
+    pub copyright: CopyrightConfig,
+
     /// Number of parallel jobs (None = num_cpus)
     pub parallel_jobs: Option<usize>,
 
@@ -33,23 +108,268 @@ pub struct Config {
 }
 
 /// Comment style configuration for different file types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Besides a simple `prefix`/`suffix` pair, a style can describe a block layout:
+///
+/// ```toml
+/// [comment_styles]
+/// java = { opening = "/*", line_prefix = " *", closing = " */" }
+/// cpp = { opening = "////", line_prefix = "//", closing = "////", blank_line_after = false }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct CommentStyleConfig {
     /// The comment prefix (e.g., "//", "#", "/*")
+    #[serde(default)]
     pub prefix: String,
     /// Optional comment suffix (e.g., "*/" for block comments)
     #[serde(default)]
     pub suffix: Option<String>,
+    /// Optional opening line emitted before the header (e.g., "/*", "////")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opening: Option<String>,
+    /// Optional decoration placed before each header line (e.g., " *")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_prefix: Option<String>,
+    /// Optional closing line emitted after the header (e.g., " */", "////")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closing: Option<String>,
+    /// Whether a blank line separates the header from the code (default: true)
+    #[serde(default = "default_true")]
+    pub blank_line_after: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Paths holding third-party code and the licenses accepted there
+///
+/// Files matching `paths` pass with our header or with an accepted license:
+///
+/// ```toml
+/// [[third_party]]
+/// paths = ["vendor/**", "third_party/**"]
+/// allowed_licenses = ["MIT", "Apache-2.0", "BSD-3-Clause"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ThirdPartyRule {
+    /// Gitignore-style patterns, relative to the scan root (or a nested config's directory)
+    pub paths: Vec<String>,
+    /// SPDX identifiers accepted in these files (empty = any license)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_licenses: Vec<String>,
+    /// Fail files that carry no license at all (default: true)
+    #[serde(default = "default_true")]
+    pub require_license: bool,
+}
+
+impl ThirdPartyRule {
+    /// Create a rule for `paths` accepting any license
+    pub fn new<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+            allowed_licenses: Vec::new(),
+            require_license: true,
+        }
+    }
+
+    /// Accept a license by SPDX identifier
+    pub fn with_allowed_license(mut self, id: impl Into<String>) -> Self {
+        self.allowed_licenses.push(id.into());
+        self
+    }
+
+    /// Whether a file with this license passes the rule
+    ///
+    /// Unrecognised license text only passes when no allow-list is configured.
+    pub fn allows(&self, license: &LicenseClassification) -> bool {
+        match license {
+            LicenseClassification::Ours => true,
+            LicenseClassification::Known { id } => {
+                self.allowed_licenses.is_empty()
+                    || self.allowed_licenses.iter().any(|allowed| allowed.eq_ignore_ascii_case(id))
+            }
+            LicenseClassification::Unknown => self.allowed_licenses.is_empty(),
+            LicenseClassification::None => !self.require_license,
+        }
+    }
+}
+
+impl CommentStyleConfig {
+    /// Create a line comment style (e.g., "//", "#")
+    pub fn line_comment(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            suffix: None,
+            opening: None,
+            line_prefix: None,
+            closing: None,
+            blank_line_after: true,
+        }
+    }
+
+    /// Create a block comment style (e.g., "/*" and "*/")
+    pub fn block_comment(prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        Self { suffix: Some(suffix.into()), ..Self::line_comment(prefix) }
+    }
+
+    /// Create a layout style with opening, per-line and closing parts
+    pub fn layout(
+        opening: impl Into<String>,
+        line_prefix: impl Into<String>,
+        closing: impl Into<String>,
+    ) -> Self {
+        Self {
+            opening: Some(opening.into()),
+            line_prefix: Some(line_prefix.into()),
+            closing: Some(closing.into()),
+            ..Self::line_comment(String::new())
+        }
+    }
+}
+
+/// License policy: which SPDX identifiers may appear in the tree
+///
+/// Path rules take precedence over the global lists, and within a list `deny`
+/// beats `review` beats `allow`. Once an `allow` list applies to a file, any
+/// license it doesn't list is denied.
+///
+/// ```toml
+/// [policy]
+/// allow = ["MIT", "Apache-2.0", "BSD-3-Clause"]
+/// review = ["MPL-2.0"]
+///
+/// [[policy.paths]]
+/// paths = ["services/"]
+/// deny = ["AGPL-3.0-only", "AGPL-3.0-or-later"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Licenses allowed everywhere
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Licenses that fail the check
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Licenses reported for review without failing the check
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub review: Vec<String>,
+    /// Rules for files under particular paths
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<PolicyPathRule>,
+}
+
+impl PolicyConfig {
+    /// Returns true if no policy is configured
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty()
+            && self.deny.is_empty()
+            && self.review.is_empty()
+            && self.paths.is_empty()
+    }
+}
+
+/// License policy for the files matching `paths`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyPathRule {
+    /// Gitignore-style patterns, relative to the scan root (or a nested config's directory)
+    pub paths: Vec<String>,
+    /// Licenses allowed under these paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Licenses that fail the check under these paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Licenses reported for review under these paths
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub review: Vec<String>,
+}
+
+/// Copyright holders allowed in file headers
+///
+/// Once `holders` is set, statements naming anyone else are flagged: as an
+/// individual when the holder looks like a person's name, otherwise as unknown.
+/// Spelling variants of a holder (listed in `aliases`, or differing only in case
+/// and punctuation) are reported and rewritten to the canonical name by `--fix`.
+///
+/// With `git_history`, each file's statements are also compared with its local
+/// git history: the years must cover its first and last commit, and its authors
+/// must be named unless a known holder or an organisation holds the copyright.
+///
+/// ```toml
+/// [copyright]
+/// holders = ["Acme Corp", "Jane Doe"]
+/// aliases = { "ACME Corporation" = "Acme Corp", "Acme Inc." = "Acme Corp" }
+/// git_history = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct CopyrightConfig {
+    /// Allowed holders, in their canonical spelling
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub holders: Vec<String>,
+    /// Spelling variants mapped to their canonical holder
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Compare statement years and holders with the git history of each file
+    pub git_history: bool,
+}
+
+impl CopyrightConfig {
+    /// Returns true if no holders or aliases are configured and git history is off
+    pub fn is_empty(&self) -> bool {
+        self.holders.is_empty() && self.aliases.is_empty() && !self.git_history
+    }
+}
+
+impl From<&CommentStyleConfig> for CommentStyle {
+    fn from(config: &CommentStyleConfig) -> Self {
+        Self {
+            prefix: config.prefix.clone(),
+            suffix: config.suffix.clone(),
+            opening: config.opening.clone(),
+            line_prefix: config.line_prefix.clone(),
+            closing: config.closing.clone(),
+            blank_line_after: config.blank_line_after,
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            extends: vec![],
             license_header: String::new(),
             comment_styles: default_comment_styles(),
+            filename_comment_styles: default_filename_comment_styles(),
+            glob_comment_styles: HashMap::new(),
+            interpreter_comment_styles: default_interpreter_comment_styles(),
             ignore_patterns: vec![],
+            include_hidden: false,
+            follow_symlinks: false,
+            max_depth: None,
+            respect_gitignore: true,
+            ignore_filename: ".flcignore".to_string(),
+            include_patterns: vec![],
+            only_extensions: vec![],
+            only_languages: vec![],
             max_header_bytes: 8192,
+            max_file_size: None,
             skip_empty_files: true,
+            fail_on_unreadable: false,
+            require_suppression_reason: false,
+            check_packages: false,
+            third_party: vec![],
+            policy: PolicyConfig::default(),
+            copyright: CopyrightConfig::default(),
             parallel_jobs: None,
             similarity_threshold: 70,
         }
@@ -78,18 +398,96 @@ impl Config {
         self
     }
 
+    /// Add a custom comment style for an exact file name
+    pub fn with_filename_comment_style(
+        mut self,
+        file_name: impl Into<String>,
+        style: CommentStyleConfig,
+    ) -> Self {
+        self.filename_comment_styles.insert(file_name.into(), style);
+        self
+    }
+
+    /// Add a custom comment style for a glob pattern
+    pub fn with_glob_comment_style(
+        mut self,
+        pattern: impl Into<String>,
+        style: CommentStyleConfig,
+    ) -> Self {
+        self.glob_comment_styles.insert(pattern.into(), style);
+        self
+    }
+
+    /// Add a custom comment style for a shebang interpreter or modeline language
+    pub fn with_interpreter_comment_style(
+        mut self,
+        interpreter: impl Into<String>,
+        style: CommentStyleConfig,
+    ) -> Self {
+        self.interpreter_comment_styles.insert(interpreter.into(), style);
+        self
+    }
+
     /// Add an ignore pattern
     pub fn with_ignore_pattern(mut self, pattern: impl Into<String>) -> Self {
         self.ignore_patterns.push(pattern.into());
         self
     }
 
+    /// Add an include pattern; only files matching an include pattern are checked
+    pub fn with_include_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.include_patterns.push(pattern.into());
+        self
+    }
+
+    /// Only check files with the given extension
+    pub fn with_only_extension(mut self, extension: impl Into<String>) -> Self {
+        self.only_extensions.push(extension.into());
+        self
+    }
+
+    /// Only check files of the given language
+    pub fn with_only_language(mut self, language: impl Into<String>) -> Self {
+        self.only_languages.push(language.into());
+        self
+    }
+
     /// Set maximum header bytes
     pub fn with_max_header_bytes(mut self, bytes: usize) -> Self {
         self.max_header_bytes = bytes;
         self
     }
 
+    /// Skip files larger than the given number of bytes
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Count unreadable files as failures instead of skipping them
+    pub fn with_fail_on_unreadable(mut self, fail: bool) -> Self {
+        self.fail_on_unreadable = fail;
+        self
+    }
+
+    /// Require a justification after suppression markers
+    pub fn with_require_suppression_reason(mut self, require: bool) -> Self {
+        self.require_suppression_reason = require;
+        self
+    }
+
+    /// Check each package's license file against its headers
+    pub fn with_check_packages(mut self, check: bool) -> Self {
+        self.check_packages = check;
+        self
+    }
+
+    /// Add a third-party path rule
+    pub fn with_third_party(mut self, rule: ThirdPartyRule) -> Self {
+        self.third_party.push(rule);
+        self
+    }
+
     /// Set similarity threshold
     pub fn with_similarity_threshold(mut self, threshold: u8) -> Self {
         self.similarity_threshold = threshold.min(100);
@@ -117,8 +515,7 @@ fn default_comment_styles() -> HashMap<String, CommentStyleConfig> {
         "scala", "go", "swift", "cs", "vb", "fs", "ml", "fsx", "elm",
     ];
     for ext in slash_slash_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: "//".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("//"));
     }
 
     // Line comments with "#"
@@ -128,71 +525,173 @@ fn default_comment_styles() -> HashMap<String, CommentStyleConfig> {
         "nimble", "cr", "rspec", "thor",
     ];
     for ext in hash_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: "#".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("#"));
     }
 
     // Block comments with "<!--" and "-->"
     let html_extensions = vec!["html", "htm", "xml", "svg", "vue", "jsx", "tsx", "xsd"];
     for ext in html_extensions {
-        styles.insert(
-            ext.to_string(),
-            CommentStyleConfig { prefix: "<!--".to_string(), suffix: Some("-->".to_string()) },
-        );
+        styles.insert(ext.to_string(), CommentStyleConfig::block_comment("<!--", "-->"));
     }
 
     // Block comments with "/*" and "*/"
     let css_extensions = vec!["css", "scss", "sass", "less", "styl"];
     for ext in css_extensions {
-        styles.insert(
-            ext.to_string(),
-            CommentStyleConfig { prefix: "/*".to_string(), suffix: Some("*/".to_string()) },
-        );
+        styles.insert(ext.to_string(), CommentStyleConfig::block_comment("/*", "*/"));
     }
 
     // Line comments with "--"
     let sql_extensions = vec!["sql", "hs", "lhs"];
     for ext in sql_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: "--".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("--"));
     }
 
     // Line comments with "%"
     let erlang_extensions = vec!["erl", "hrl"];
     for ext in erlang_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: "%".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("%"));
     }
 
     // Line comments with ";;"
     let lisp_extensions = vec!["lisp", "lsp", "scm", "ss", "rkt"];
     for ext in lisp_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: ";;".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment(";;"));
     }
 
     // Line comments with "\""
     let vim_extensions = vec!["vim", "vimrc"];
     for ext in vim_extensions {
-        styles
-            .insert(ext.to_string(), CommentStyleConfig { prefix: "\"".to_string(), suffix: None });
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("\""));
     }
 
     // Line comments with "REM"
     let batch_extensions = vec!["bat", "cmd"];
     for ext in batch_extensions {
-        styles.insert(
-            ext.to_string(),
-            CommentStyleConfig { prefix: "REM".to_string(), suffix: None },
-        );
+        styles.insert(ext.to_string(), CommentStyleConfig::line_comment("REM"));
     }
 
     // Special cases
-    styles.insert("php".to_string(), CommentStyleConfig { prefix: "//".to_string(), suffix: None });
-    styles.insert("asp".to_string(), CommentStyleConfig { prefix: "'".to_string(), suffix: None });
-    styles.insert("asm".to_string(), CommentStyleConfig { prefix: ";".to_string(), suffix: None });
-    styles.insert("pas".to_string(), CommentStyleConfig { prefix: "//".to_string(), suffix: None });
-    styles.insert("d".to_string(), CommentStyleConfig { prefix: "//".to_string(), suffix: None });
+    styles.insert("php".to_string(), CommentStyleConfig::line_comment("//"));
+    styles.insert("asp".to_string(), CommentStyleConfig::line_comment("'"));
+    styles.insert("asm".to_string(), CommentStyleConfig::line_comment(";"));
+    styles.insert("pas".to_string(), CommentStyleConfig::line_comment("//"));
+    styles.insert("d".to_string(), CommentStyleConfig::line_comment("//"));
+
+    styles
+}
+
+/// Create default comment styles for common extensionless file names
+fn default_filename_comment_styles() -> HashMap<String, CommentStyleConfig> {
+    let mut styles = HashMap::new();
+
+    let hash_names = vec![
+        "Dockerfile",
+        "Containerfile",
+        "Makefile",
+        "GNUmakefile",
+        "CMakeLists.txt",
+        "BUILD",
+        "BUILD.bazel",
+        "WORKSPACE",
+        "WORKSPACE.bazel",
+        "Gemfile",
+        "Rakefile",
+        "Vagrantfile",
+        ".bashrc",
+        ".bash_profile",
+        ".zshrc",
+        ".profile",
+    ];
+    for name in hash_names {
+        styles.insert(name.to_string(), CommentStyleConfig::line_comment("#"));
+    }
+
+    styles.insert("Jenkinsfile".to_string(), CommentStyleConfig::line_comment("//"));
+
+    styles
+}
+
+/// Create default comment styles for shebang interpreters and modeline languages
+fn default_interpreter_comment_styles() -> HashMap<String, CommentStyleConfig> {
+    let mut styles = HashMap::new();
+
+    // Line comments with "#"
+    let hash_interpreters = vec![
+        "sh",
+        "bash",
+        "zsh",
+        "ksh",
+        "dash",
+        "ash",
+        "fish",
+        "shell-script",
+        "python",
+        "ruby",
+        "perl",
+        "tclsh",
+        "wish",
+        "tcl",
+        "rscript",
+        "r",
+        "awk",
+        "gawk",
+        "make",
+        "makefile",
+        "cmake",
+        "expect",
+        "pwsh",
+        "elixir",
+        "crystal",
+        "julia",
+        "yaml",
+        "toml",
+        "conf",
+        "dockerfile",
+    ];
+    for name in hash_interpreters {
+        styles.insert(name.to_string(), CommentStyleConfig::line_comment("#"));
+    }
+
+    // Line comments with "//"
+    let slash_slash_interpreters = vec![
+        "node",
+        "nodejs",
+        "deno",
+        "bun",
+        "ts-node",
+        "tsx",
+        "javascript",
+        "js",
+        "typescript",
+        "php",
+        "groovy",
+        "scala",
+        "kotlin",
+        "rust-script",
+        "go",
+        "swift",
+        "c",
+        "cpp",
+        "java",
+        "rust",
+    ];
+    for name in slash_slash_interpreters {
+        styles.insert(name.to_string(), CommentStyleConfig::line_comment("//"));
+    }
+
+    // Line comments with "--"
+    let dash_dash_interpreters =
+        vec!["lua", "luajit", "runghc", "runhaskell", "haskell", "sql", "psql"];
+    for name in dash_dash_interpreters {
+        styles.insert(name.to_string(), CommentStyleConfig::line_comment("--"));
+    }
+
+    // Line comments with ";;"
+    let lisp_interpreters =
+        vec!["racket", "guile", "sbcl", "clisp", "lisp", "scheme", "clojure", "emacs-lisp"];
+    for name in lisp_interpreters {
+        styles.insert(name.to_string(), CommentStyleConfig::line_comment(";;"));
+    }
 
     styles
 }
@@ -249,7 +748,7 @@ mod tests {
 
     #[test]
     fn comment_style_config_serialization() {
-        let style = CommentStyleConfig { prefix: "//".to_string(), suffix: Some("*/".to_string()) };
+        let style = CommentStyleConfig::block_comment("//", "*/");
 
         let serialized = serde_json::to_string(&style).unwrap();
         let deserialized: CommentStyleConfig = serde_json::from_str(&serialized).unwrap();
@@ -258,6 +757,28 @@ mod tests {
         assert_eq!(deserialized.suffix, Some("*/".to_string()));
     }
 
+    #[test]
+    fn comment_style_config_layout_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [comment_styles]
+            java = { opening = "/*", line_prefix = " *", closing = " */", blank_line_after = false }
+            "#,
+        )
+        .unwrap();
+
+        let java = config.get_comment_style("java").unwrap();
+        assert_eq!(java.prefix, "");
+        assert_eq!(java.opening.as_deref(), Some("/*"));
+        assert_eq!(java.line_prefix.as_deref(), Some(" *"));
+        assert_eq!(java.closing.as_deref(), Some(" */"));
+        assert!(!java.blank_line_after);
+
+        let style = CommentStyle::from(java);
+        assert!(style.has_layout());
+        assert!(!style.blank_line_after);
+    }
+
     #[test]
     fn config_serialization() {
         let config = Config::new().with_license_header("MIT License").with_max_header_bytes(4096);
@@ -296,6 +817,68 @@ mod tests {
         assert_eq!(css.suffix, Some("*/".to_string()));
     }
 
+    #[test]
+    fn default_filename_comment_styles_coverage() {
+        let styles = default_filename_comment_styles();
+
+        assert_eq!(styles.get("Dockerfile").unwrap().prefix, "#");
+        assert_eq!(styles.get("Makefile").unwrap().prefix, "#");
+        assert_eq!(styles.get("CMakeLists.txt").unwrap().prefix, "#");
+        assert_eq!(styles.get("Jenkinsfile").unwrap().prefix, "//");
+        assert_eq!(styles.get(".bashrc").unwrap().prefix, "#");
+    }
+
+    #[test]
+    fn default_interpreter_comment_styles_coverage() {
+        let styles = default_interpreter_comment_styles();
+
+        assert_eq!(styles.get("python").unwrap().prefix, "#");
+        assert_eq!(styles.get("bash").unwrap().prefix, "#");
+        assert_eq!(styles.get("node").unwrap().prefix, "//");
+        assert_eq!(styles.get("lua").unwrap().prefix, "--");
+    }
+
+    #[test]
+    fn filename_and_glob_styles_from_toml() {
+        let config: Config = toml::from_str(
+            r##"
+            [filename_comment_styles]
+            Jenkinsfile = { prefix = "#" }
+
+            [glob_comment_styles]
+            "*.d.ts" = { prefix = "/*", suffix = "*/" }
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(config.filename_comment_styles.get("Jenkinsfile").unwrap().prefix, "#");
+        assert_eq!(config.glob_comment_styles.get("*.d.ts").unwrap().prefix, "/*");
+    }
+
+    #[test]
+    fn third_party_rule_allows() {
+        let rule: ThirdPartyRule = toml::from_str(
+            r##"
+            paths = ["vendor/"]
+            allowed_licenses = ["MIT", "apache-2.0"]
+            "##,
+        )
+        .unwrap();
+        let known = |id: &str| LicenseClassification::Known { id: id.to_string() };
+
+        assert!(rule.require_license);
+        assert!(rule.allows(&LicenseClassification::Ours));
+        assert!(rule.allows(&known("MIT")));
+        assert!(rule.allows(&known("Apache-2.0")));
+        assert!(!rule.allows(&known("GPL-3.0-or-later")));
+        assert!(!rule.allows(&LicenseClassification::Unknown));
+        assert!(!rule.allows(&LicenseClassification::None));
+
+        let any = ThirdPartyRule { require_license: false, ..ThirdPartyRule::new(["vendor/"]) };
+        assert!(any.allows(&LicenseClassification::Unknown));
+        assert!(any.allows(&LicenseClassification::None));
+    }
+
     #[test]
     fn similarity_threshold_bounds() {
         let config = Config::new().with_similarity_threshold(150);
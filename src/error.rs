@@ -59,6 +59,50 @@ pub enum ConfigError {
         /// Description of why the value is invalid
         message: String,
     },
+
+    /// Configuration file contains a key that isn't a known field
+    #[error(
+        "Unknown field `{field}` at {location}{}",
+        suggestion.as_ref().map(|s| format!(" (did you mean `{s}`?)")).unwrap_or_default()
+    )]
+    UnknownField {
+        /// The unknown key as written
+        field: String,
+        /// Where the key appears
+        location: ConfigLocation,
+        /// Closest known field name, if any is similar enough
+        suggestion: Option<String>,
+    },
+
+    /// Configuration file is malformed or has a value of the wrong type
+    #[error("Invalid config at {location}: {message}")]
+    Syntax {
+        /// Where the problem was detected
+        location: ConfigLocation,
+        /// Description from the TOML or JSON parser
+        message: String,
+    },
+}
+
+/// Position in a configuration file, with 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLocation {
+    /// Configuration file path
+    pub path: PathBuf,
+    /// Line number (0 when unknown)
+    pub line: usize,
+    /// Column number in characters (0 when unknown)
+    pub column: usize,
+}
+
+impl std::fmt::Display for ConfigLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.path.display())
+        } else {
+            write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+        }
+    }
 }
 
 /// File scanning errors
@@ -189,6 +233,76 @@ pub use LicenseCheckerError as Error;
 /// Convenience type alias for Results with LicenseCheckerError
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Process exit status reported by the `flc` binary.
+///
+/// The numeric values are part of the CLI contract so CI can tell a broken
+/// pipeline apart from non-compliant code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitStatus {
+    /// All files compliant (scan) or all fixes applied (fix)
+    Success,
+    /// Files with missing or malformed headers were found
+    Violations,
+    /// Invalid configuration, arguments or header text
+    ConfigError,
+    /// Directory walk or file I/O failed
+    IoError,
+    /// Fix mode could not apply some of the fixes
+    FixFailed,
+    /// Files carry a recognised license other than the expected one
+    ForeignLicense,
+}
+
+impl ExitStatus {
+    /// Returns the numeric process exit code.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Success => 0,
+            Self::Violations => 1,
+            Self::ConfigError => 2,
+            Self::IoError => 3,
+            Self::FixFailed => 4,
+            Self::ForeignLicense => 5,
+        }
+    }
+
+    /// Derives the exit status of a completed scan or fix run.
+    pub fn from_summary(summary: &crate::types::ScanSummary) -> Self {
+        if summary.fix_errors > 0 {
+            Self::FixFailed
+        } else if summary.unreadable.iter().any(|result| result.status.is_unreadable()) {
+            Self::IoError
+        } else if !summary.foreign_licenses.is_empty() {
+            Self::ForeignLicense
+        } else if summary.failed > 0 || !summary.packages.is_empty() {
+            Self::Violations
+        } else {
+            Self::Success
+        }
+    }
+}
+
+impl From<ExitStatus> for std::process::ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status.code())
+    }
+}
+
+impl LicenseCheckerError {
+    /// Maps the error to the exit status the CLI reports for it.
+    pub fn exit_status(&self) -> ExitStatus {
+        match self {
+            // Generic errors come from header text validation
+            Self::Config(_) | Self::Validation(_) | Self::Generic(_) => ExitStatus::ConfigError,
+            Self::Scanner(_) | Self::Checker(_) => ExitStatus::IoError,
+            Self::Fixer(FixerError::ReadError { .. } | FixerError::WriteError { .. }) => {
+                ExitStatus::IoError
+            }
+            Self::Fixer(_) => ExitStatus::FixFailed,
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -201,6 +315,24 @@ mod tests {
         assert_eq!(error.to_string(), "Configuration error: Config file not found: /tmp/test.toml");
     }
 
+    #[test]
+    fn config_error_display_with_location() {
+        let location = ConfigLocation { path: PathBuf::from(".flc.toml"), line: 3, column: 1 };
+        let error = ConfigError::UnknownField {
+            field: "ignore_pattern".to_string(),
+            location: location.clone(),
+            suggestion: Some("ignore_patterns".to_string()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Unknown field `ignore_pattern` at .flc.toml:3:1 (did you mean `ignore_patterns`?)"
+        );
+
+        let location = ConfigLocation { line: 0, column: 0, ..location };
+        let error = ConfigError::Syntax { location, message: "bad".to_string() };
+        assert_eq!(error.to_string(), "Invalid config at .flc.toml: bad");
+    }
+
     #[test]
     fn config_error_display() {
         let error = ConfigError::NotFound(PathBuf::from("/tmp/test.toml"));
@@ -289,6 +421,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn exit_status_from_error() {
+        let config: LicenseCheckerError = ConfigError::MissingField { field: "x" }.into();
+        assert_eq!(config.exit_status(), ExitStatus::ConfigError);
+
+        let validation: LicenseCheckerError = ValidationError::EmptyHeader.into();
+        assert_eq!(validation.exit_status(), ExitStatus::ConfigError);
+
+        let scanner: LicenseCheckerError = ScannerError::Io {
+            path: PathBuf::from("a.rs"),
+            source: std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        }
+        .into();
+        assert_eq!(scanner.exit_status(), ExitStatus::IoError);
+
+        let fixer: LicenseCheckerError =
+            FixerError::IdempotencyViolation(PathBuf::from("a.rs")).into();
+        assert_eq!(fixer.exit_status(), ExitStatus::FixFailed);
+    }
+
+    #[test]
+    fn exit_status_from_summary() {
+        use crate::types::ScanSummary;
+        use std::time::Duration;
+
+        let clean = ScanSummary::new(2, 2, 0, 0, Duration::ZERO);
+        assert_eq!(ExitStatus::from_summary(&clean), ExitStatus::Success);
+
+        let dirty = ScanSummary::new(2, 1, 1, 0, Duration::ZERO);
+        assert_eq!(ExitStatus::from_summary(&dirty), ExitStatus::Violations);
+
+        let partial = ScanSummary::new(2, 1, 1, 0, Duration::ZERO).with_fix_errors(1);
+        assert_eq!(ExitStatus::from_summary(&partial), ExitStatus::FixFailed);
+        assert_eq!(ExitStatus::FixFailed.code(), 4);
+
+        let foreign = ScanSummary::new(2, 1, 1, 0, Duration::ZERO).with_foreign_licenses(vec![
+            crate::types::ScanResult::new(
+                crate::types::FilePath::new(PathBuf::from("a.rs")),
+                crate::types::FileStatus::ForeignLicense { detected: "GPL-3.0-only".to_string() },
+            ),
+        ]);
+        assert_eq!(ExitStatus::from_summary(&foreign), ExitStatus::ForeignLicense);
+        assert_eq!(ExitStatus::ForeignLicense.code(), 5);
+    }
+
     #[test]
     fn license_checker_error_from_string() {
         let error_str = "some validation error".to_string();
@@ -0,0 +1,195 @@
+//! Copyright holder normalisation and year updates.
+//!
+//! Rewrites spelling variants of configured copyright holders in a file's header
+//! to their canonical form, and the years of its primary statement to cover the
+//! file's git history, leaving the rest of each statement untouched.
+
+use crate::checker::copyright::{
+    bare_holder, copyright_statement, copyrights, parse_copyright, HolderPolicy, HEADER_LINES,
+};
+use crate::checker::history::FileHistory;
+use crate::checker::prelude::effective_header_start;
+
+/// Replace holder spelling variants in the header with their canonical form
+///
+/// Returns `None` if the content is not UTF-8 or no statement needs changing.
+#[tracing::instrument(skip(content, holders))]
+pub fn normalize_holders(content: &[u8], holders: &HolderPolicy<'_>) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(content).ok()?;
+    let start = effective_header_start(content);
+    let (prelude, region) = (text.get(..start)?, text.get(start..)?);
+
+    let mut changed = false;
+    let mut out = String::with_capacity(text.len());
+    out.push_str(prelude);
+    for (index, line) in region.split_inclusive('\n').enumerate() {
+        let normalized = (index < HEADER_LINES).then(|| normalize_line(line, holders)).flatten();
+        changed |= normalized.is_some();
+        out.push_str(normalized.as_deref().unwrap_or(line));
+    }
+
+    changed.then(|| out.into_bytes())
+}
+
+/// Widen the years of the primary statement to cover the file's commits
+///
+/// `Copyright 2019 Acme Corp` committed until 2026 becomes
+/// `Copyright 2019-2026 Acme Corp`; a statement without years gains the range
+/// before its holder. Returns `None` if the content is not UTF-8 or the years
+/// already cover the history.
+#[tracing::instrument(skip(content, history, holders))]
+pub fn update_years(
+    content: &[u8],
+    history: &FileHistory,
+    holders: &HolderPolicy<'_>,
+) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(content).ok()?;
+    let found = copyrights(content);
+    let primary = holders.primary(&found)?;
+    let span = primary.year_span();
+    if span.is_some_and(|span| span.covers(history.years)) {
+        return None;
+    }
+    let years = span.map_or(history.years, |span| span.union(history.years)).to_string();
+
+    let start = effective_header_start(content);
+    let (prelude, region) = (text.get(..start)?, text.get(start..)?);
+    let mut updated = false;
+    let mut out = String::with_capacity(text.len().saturating_add(5));
+    out.push_str(prelude);
+    for (index, line) in region.split_inclusive('\n').enumerate() {
+        let is_primary = !updated
+            && index < HEADER_LINES
+            && copyright_statement(line).is_some_and(|statement| statement == primary.statement);
+        let replaced = is_primary.then(|| replace_years(line, &primary.holder, &years)).flatten();
+        updated |= replaced.is_some();
+        out.push_str(replaced.as_deref().unwrap_or(line));
+    }
+
+    updated.then(|| out.into_bytes())
+}
+
+/// The line with the years before `holder` replaced by `years`
+fn replace_years(line: &str, holder: &str, years: &str) -> Option<String> {
+    let holder_start = line.find(holder)?;
+    // The year list ends right before the holder: "2019-2024, " or "2020 "
+    let kept = line
+        .get(..holder_start)?
+        .trim_end_matches(|c: char| c.is_ascii_digit() || " -–,:".contains(c));
+    Some(format!("{} {} {}", kept, years, line.get(holder_start..)?))
+}
+
+/// The line with its holder replaced, if it is a statement naming a variant
+fn normalize_line(line: &str, holders: &HolderPolicy<'_>) -> Option<String> {
+    let statement = copyright_statement(line)?;
+    let holder = parse_copyright(&statement)?.holder;
+    // Punctuation after the holder stays in the line, so the canonical name is
+    // spliced in without its own: "ACME Corporation." becomes "Acme Corp." for
+    // either "Acme Corp" or "Acme Corp." as canonical
+    let canonical = bare_holder(holders.canonical(&holder)?);
+    if canonical == holder {
+        return None;
+    }
+
+    // The holder follows the keyword, symbols and years
+    let holder_start = statement.find(&holder)?;
+    let start = line.find(&statement)?.checked_add(holder_start)?;
+    let holder_end = start.checked_add(holder.len())?;
+
+    Some(format!("{}{}{}", line.get(..start)?, canonical, line.get(holder_end..)?))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::checker::copyright::YearRange;
+    use crate::config::CopyrightConfig;
+
+    #[test]
+    fn rewrites_variants_in_the_header() {
+        let config: CopyrightConfig = toml::from_str(
+            r#"
+            holders = ["Acme Corp"]
+            aliases = { "ACME Corporation" = "Acme Corp" }
+            "#,
+        )
+        .unwrap();
+        let holders = HolderPolicy::new(&config);
+
+        let content =
+            "#!/bin/sh\n# Copyright (c) 2019-2024 ACME Corporation. All rights reserved.\r\n\
+                       # Copyright 2020 acme corp\n# Copyright 2021 Acme Corp\necho hi\n";
+        let fixed = normalize_holders(content.as_bytes(), &holders).unwrap();
+        assert_eq!(
+            String::from_utf8(fixed).unwrap(),
+            "#!/bin/sh\n# Copyright (c) 2019-2024 Acme Corp. All rights reserved.\r\n\
+             # Copyright 2020 Acme Corp\n# Copyright 2021 Acme Corp\necho hi\n"
+        );
+
+        let canonical = "// Copyright 2021 Acme Corp\nfn main() {}\n";
+        assert_eq!(normalize_holders(canonical.as_bytes(), &holders), None);
+    }
+
+    #[test]
+    fn widens_years_to_the_history() {
+        let config: CopyrightConfig = toml::from_str(r#"holders = ["Acme Corp"]"#).unwrap();
+        let holders = HolderPolicy::new(&config);
+        let history = FileHistory {
+            years: YearRange { first: 2019, last: 2026 },
+            authors: ["Jane Doe".to_string()].into_iter().collect(),
+        };
+        let update = |content: &str| {
+            update_years(content.as_bytes(), &history, &holders)
+                .map(|fixed| String::from_utf8(fixed).unwrap())
+        };
+
+        assert_eq!(
+            update("// Copyright 2010 Jane Doe\n// Copyright (c) 2020-2024 Acme Corp. All rights reserved.\r\n").unwrap(),
+            "// Copyright 2010 Jane Doe\n// Copyright (c) 2019-2026 Acme Corp. All rights reserved.\r\n"
+        );
+        assert_eq!(
+            update("#!/bin/sh\n# Copyright Acme Corp\necho hi\n").unwrap(),
+            "#!/bin/sh\n# Copyright 2019-2026 Acme Corp\necho hi\n"
+        );
+        assert_eq!(
+            update("/* Copyright 2018, 2021 Acme Corp */\n").unwrap(),
+            "/* Copyright 2018-2026 Acme Corp */\n"
+        );
+        assert_eq!(update("// Copyright 2019-2026 Acme Corp\n"), None);
+        assert_eq!(update("fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn keeps_punctuation_of_canonical_holders() {
+        let config: CopyrightConfig = toml::from_str(
+            r#"
+            holders = ["Acme Corp."]
+            aliases = { "ACME Corporation" = "Acme Corp." }
+            "#,
+        )
+        .unwrap();
+        let holders = HolderPolicy::new(&config);
+
+        let canonical = "// Copyright (c) 2024 Acme Corp.\n\
+                         // Copyright (c) 2020 Acme Corp. All rights reserved.\n";
+        assert_eq!(normalize_holders(canonical.as_bytes(), &holders), None);
+
+        let variant = "// Copyright (c) 2020 ACME Corporation. All rights reserved.\n";
+        assert_eq!(
+            String::from_utf8(normalize_holders(variant.as_bytes(), &holders).unwrap()).unwrap(),
+            "// Copyright (c) 2020 Acme Corp. All rights reserved.\n"
+        );
+
+        let history = FileHistory {
+            years: YearRange { first: 2019, last: 2026 },
+            authors: Default::default(),
+        };
+        let updated = update_years(canonical.as_bytes(), &history, &holders).unwrap();
+        assert_eq!(
+            String::from_utf8(updated).unwrap(),
+            "// Copyright (c) 2019-2026 Acme Corp.\n\
+             // Copyright (c) 2020 Acme Corp. All rights reserved.\n"
+        );
+    }
+}
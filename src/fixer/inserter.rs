@@ -3,13 +3,23 @@
 //! Provides functions to format license headers with appropriate comment styles
 //! and insert them into source files at the correct positions.
 
-use crate::checker::prelude::header_start_offset;
+use crate::checker::detector::leading_comment;
+use crate::checker::licenses::identify_license;
+use crate::checker::prelude::{effective_header_start_for, PreludeLanguage};
 use crate::error::Result;
-use crate::types::{CommentStyle, LicenseHeader};
+use crate::types::{CommentStyle, LicenseClassification, LicenseHeader, LineEnding};
 
 /// Format license header with appropriate comment style
 #[tracing::instrument(skip(header))]
 pub fn format_header(header: &LicenseHeader, style: &CommentStyle) -> String {
+    if style.has_layout() {
+        let mut result = style.render_layout(header);
+        if style.blank_line_after {
+            result.push('\n');
+        }
+        return result;
+    }
+
     let mut result = String::new();
 
     for line in header.as_str().lines() {
@@ -26,7 +36,9 @@ pub fn format_header(header: &LicenseHeader, style: &CommentStyle) -> String {
     }
 
     // Add blank line after header
-    result.push('\n');
+    if style.blank_line_after {
+        result.push('\n');
+    }
 
     result
 }
@@ -38,8 +50,22 @@ pub fn insert_header(
     header: &LicenseHeader,
     style: &CommentStyle,
 ) -> Result<Vec<u8>> {
-    let insert_offset = header_start_offset(content);
-    let formatted = format_header(header, style);
+    insert_header_for(content, header, style, PreludeLanguage::Generic)
+}
+
+/// Insert header after the language's prelude (the same offset the checker uses)
+///
+/// The header is emitted in the file's dominant line ending, and a leading UTF-8
+/// BOM stays the first bytes of the file since it is part of the prelude.
+#[tracing::instrument(skip(content, header))]
+pub fn insert_header_for(
+    content: &[u8],
+    header: &LicenseHeader,
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> Result<Vec<u8>> {
+    let insert_offset = effective_header_start_for(content, language);
+    let formatted = LineEnding::detect(content).apply(&format_header(header, style));
 
     let mut result = Vec::with_capacity(content.len().saturating_add(formatted.len()));
 
@@ -59,15 +85,52 @@ pub fn insert_header(
     Ok(result)
 }
 
+/// Replace the foreign license comment after the prelude with the header
+///
+/// Used to overwrite a foreign license: the leading comment block (see
+/// [`leading_comment`]) and the blank lines after it are replaced, but only when
+/// that block is recognised as a license. Doc comments never belong to the block,
+/// so module docs stay; otherwise the header is inserted above what is there.
+#[tracing::instrument(skip(content, header))]
+pub fn replace_leading_comment(
+    content: &[u8],
+    header: &LicenseHeader,
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> Result<Vec<u8>> {
+    let block = leading_comment(content, style, language);
+    let license = identify_license(content.get(block.clone()).unwrap_or(&[]));
+    let end = if matches!(license, LicenseClassification::Known { .. }) {
+        block.end
+    } else {
+        block.start
+    };
+
+    let mut result = content.get(..block.start).unwrap_or(&[]).to_vec();
+    result.extend_from_slice(content.get(end..).unwrap_or(&[]));
+    insert_header_for(&result, header, style, language)
+}
+
 /// Check if content already contains a license header
 #[tracing::instrument(skip(content, header))]
 pub fn contains_header(content: &[u8], header: &LicenseHeader, style: &CommentStyle) -> bool {
-    let formatted = format_header(header, style);
+    contains_header_for(content, header, style, PreludeLanguage::Generic)
+}
+
+/// Check if content contains the license header right after the language's prelude
+#[tracing::instrument(skip(content, header))]
+pub fn contains_header_for(
+    content: &[u8],
+    header: &LicenseHeader,
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> bool {
+    let formatted = LineEnding::detect(content).apply(&format_header(header, style));
     let formatted_bytes = formatted.as_bytes();
 
     // Look for the formatted header in the content
     // Start from the header insertion point
-    let start_offset = header_start_offset(content);
+    let start_offset = effective_header_start_for(content, language);
 
     // Use safe bounds checking instead of array indexing
     if let Some(end_offset) = start_offset.checked_add(formatted_bytes.len()) {
@@ -91,10 +154,21 @@ pub fn remove_header(
     header: &LicenseHeader,
     style: &CommentStyle,
 ) -> Result<Vec<u8>> {
-    let formatted = format_header(header, style);
+    remove_header_for(content, header, style, PreludeLanguage::Generic)
+}
+
+/// Remove the license header found right after the language's prelude
+#[tracing::instrument(skip(content, header))]
+pub fn remove_header_for(
+    content: &[u8],
+    header: &LicenseHeader,
+    style: &CommentStyle,
+    language: PreludeLanguage,
+) -> Result<Vec<u8>> {
+    let formatted = LineEnding::detect(content).apply(&format_header(header, style));
     let formatted_bytes = formatted.as_bytes();
 
-    let start_offset = header_start_offset(content);
+    let start_offset = effective_header_start_for(content, language);
 
     // Check if header exists at expected location using safe bounds checking
     if let Some(end_offset) = start_offset.checked_add(formatted_bytes.len()) {
@@ -134,11 +208,11 @@ mod tests {
     }
 
     fn create_line_style() -> CommentStyle {
-        CommentStyle { prefix: "//".to_string(), suffix: None }
+        CommentStyle::line_comment("//")
     }
 
     fn create_block_style() -> CommentStyle {
-        CommentStyle { prefix: "/*".to_string(), suffix: Some("*/".to_string()) }
+        CommentStyle::block_comment("/*", "*/")
     }
 
     #[test]
@@ -163,6 +237,39 @@ mod tests {
         assert_eq!(formatted, expected);
     }
 
+    #[test]
+    fn format_header_javadoc_layout() {
+        let header = create_test_header();
+        let style = CommentStyle::javadoc_style();
+
+        let formatted = format_header(&header, &style);
+        let expected = "/*\n * MIT License\n *\n * Copyright 2024 Test\n */\n\n";
+
+        assert_eq!(formatted, expected);
+    }
+
+    #[test]
+    fn format_header_without_blank_line_after() {
+        let header = create_test_header();
+        let style = create_line_style().with_blank_line_after(false);
+
+        let formatted = format_header(&header, &style);
+        let expected = "// MIT License\n//\n// Copyright 2024 Test\n";
+
+        assert_eq!(formatted, expected);
+    }
+
+    #[test]
+    fn inserted_layout_header_is_detected() {
+        use crate::checker::detector::{detect_header, HeaderMatch};
+
+        let header = create_test_header();
+        let style = CommentStyle::layout("////", "//", "////");
+
+        let result = insert_header(b"int main() {}\n", &header, &style).unwrap();
+        assert_eq!(detect_header(&result, &header, &style), HeaderMatch::Exact);
+    }
+
     #[test]
     fn insert_header_at_start() {
         let header = create_test_header();
@@ -191,6 +298,67 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn insert_header_after_encoding_line() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let content = b"# -*- coding: utf-8 -*-\nprint(1)\n";
+        let result = insert_header(content, &header, &style).unwrap();
+
+        let expected =
+            b"# -*- coding: utf-8 -*-\n// MIT License\n//\n// Copyright 2024 Test\n\nprint(1)\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn insert_header_after_language_prelude_is_detected() {
+        use crate::checker::detector::{detect_header_for, HeaderMatch};
+
+        let header = create_test_header();
+        let style = CommentStyle::layout("////", "//", "////");
+        let cases: [(&[u8], PreludeLanguage); 4] = [
+            (b"<?php\necho 'hi';\n", PreludeLanguage::Php),
+            (b"//go:build linux\n\npackage main\n", PreludeLanguage::Go),
+            (b"---\ntitle: Post\n---\nBody\n", PreludeLanguage::Markdown),
+            (b"# syntax=docker/dockerfile:1\nFROM alpine\n", PreludeLanguage::Dockerfile),
+        ];
+
+        for (content, language) in cases {
+            let result = insert_header_for(content, &header, &style, language).unwrap();
+            assert!(result
+                .ends_with(content.get(effective_header_start_for(content, language)..).unwrap()));
+            assert_eq!(detect_header_for(&result, &header, &style, language), HeaderMatch::Exact);
+        }
+    }
+
+    #[test]
+    fn insert_header_preserves_crlf() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let content = b"#!/bin/sh\r\necho hello\r\n";
+        let result = insert_header(content, &header, &style).unwrap();
+
+        let expected =
+            b"#!/bin/sh\r\n// MIT License\r\n//\r\n// Copyright 2024 Test\r\n\r\necho hello\r\n";
+        assert_eq!(result, expected);
+        assert_eq!(LineEnding::detect(&result), LineEnding::CrLf);
+        assert!(contains_header(&result, &header, &style));
+    }
+
+    #[test]
+    fn insert_header_keeps_bom_first() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let content = b"\xEF\xBB\xBFfn main() {}\n";
+        let result = insert_header(content, &header, &style).unwrap();
+
+        let expected = b"\xEF\xBB\xBF// MIT License\n//\n// Copyright 2024 Test\n\nfn main() {}\n";
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn contains_header_present() {
         let header = create_test_header();
@@ -247,6 +415,22 @@ mod tests {
         assert_eq!(result, content);
     }
 
+    #[test]
+    fn contains_and_remove_header_after_language_prelude() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let original = "<?php\necho 'hi';\n";
+        let language =
+            PreludeLanguage::detect(std::path::Path::new("index.php"), original.as_bytes());
+        let content = insert_header_for(original.as_bytes(), &header, &style, language).unwrap();
+
+        assert!(contains_header_for(&content, &header, &style, language));
+        assert!(!contains_header(&content, &header, &style));
+        let result = remove_header_for(&content, &header, &style, language).unwrap();
+        assert_eq!(std::str::from_utf8(&result).unwrap(), original);
+    }
+
     #[test]
     fn insert_header_preserves_content() {
         let header = create_test_header();
@@ -259,4 +443,60 @@ mod tests {
         let result_str = std::str::from_utf8(&result).unwrap();
         assert!(result_str.ends_with(original));
     }
+
+    #[test]
+    fn replace_leading_comment_line_style() {
+        let header = create_test_header();
+        let style = create_line_style();
+
+        let original =
+            "// SPDX-License-Identifier: GPL-2.0-only\n// Copyright 2019 Other\n\nfn main() {}\n";
+        let result =
+            replace_leading_comment(original.as_bytes(), &header, &style, PreludeLanguage::Generic)
+                .unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&result).unwrap(),
+            "// MIT License\n//\n// Copyright 2024 Test\n\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn replace_leading_comment_keeps_module_docs() {
+        let header = create_test_header();
+        let style = create_line_style();
+        let replace = |original: &str| {
+            let result = replace_leading_comment(
+                original.as_bytes(),
+                &header,
+                &style,
+                PreludeLanguage::Generic,
+            )
+            .unwrap();
+            String::from_utf8(result).unwrap()
+        };
+
+        let docs =
+            "//! Talks to the GNU General Public License version 3 tool chain\n\nfn main() {}\n";
+        assert_eq!(replace(docs), format!("{}{}", format_header(&header, &style), docs));
+
+        let original = format!("// SPDX-License-Identifier: GPL-2.0-only\n{}", docs);
+        assert_eq!(replace(&original), format!("{}{}", format_header(&header, &style), docs));
+    }
+
+    #[test]
+    fn replace_leading_comment_block_style() {
+        let header = create_test_header();
+        let style = CommentStyle::javadoc_style();
+
+        let original = "/*\n * Licensed under the Apache License, Version 2.0\n */\npackage a;\n";
+        let result =
+            replace_leading_comment(original.as_bytes(), &header, &style, PreludeLanguage::Generic)
+                .unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&result).unwrap(),
+            "/*\n * MIT License\n *\n * Copyright 2024 Test\n */\n\npackage a;\n"
+        );
+    }
 }
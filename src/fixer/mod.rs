@@ -3,6 +3,7 @@
 //! Provides the main interface for adding license headers to files that are missing them,
 //! with atomic writes and comprehensive error handling.
 
+pub mod copyright;
 pub mod inserter;
 pub mod writer;
 
@@ -11,32 +12,41 @@ use std::path::Path;
 use tracing::{debug, info};
 
 use crate::{
-    checker::HeaderChecker,
     config::Config,
     error::{FixerError, Result},
-    scanner::walker::{FileWalker, WalkEntry},
-    types::header_types::CommentStyle,
-    types::{FilePath, FileStatus, ScanResult, ScanSummary, SkipReason},
+    scanner::hierarchy::ConfigHierarchy,
+    scanner::walker::{access_error_reason, FileWalker, WalkEntry},
+    types::{
+        CopyrightResult, FilePath, FileStatus, PolicyVerdict, ScanResult, ScanSummary, SkipReason,
+    },
 };
 
 /// Main interface for fixing license headers in files.
 #[derive(Debug)]
 pub struct HeaderFixer {
     walker: FileWalker,
-    checker: HeaderChecker,
+    hierarchy: ConfigHierarchy,
     config: Config,
+    force: bool,
 }
 
 impl HeaderFixer {
     /// Creates a new HeaderFixer with the given configuration.
     #[tracing::instrument(skip(config))]
     pub fn new(root: &Path, config: Config) -> Result<Self> {
-        let walker = FileWalker::new(root)
-            .with_ignores(config.ignore_patterns.clone())
+        let walker = FileWalker::for_config(root, &config)?
             .with_parallelism(config.parallel_jobs.unwrap_or(1));
-        let checker = HeaderChecker::new(&config)?;
+        let hierarchy = ConfigHierarchy::new(root, config.clone())?;
 
-        Ok(Self { walker, checker, config })
+        Ok(Self { walker, hierarchy, config, force: false })
+    }
+
+    /// Overwrite headers carrying a different, recognised license.
+    ///
+    /// Without this, such files are reported and left untouched.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
     }
 
     /// Fixes all files that are missing license headers.
@@ -51,18 +61,80 @@ impl HeaderFixer {
         let start = Instant::now();
 
         // Get all files and their status
-        let entries: Vec<WalkEntry> = self.walker.walk().collect::<Result<Vec<_>>>()?;
+        let entries: Vec<Result<WalkEntry>> = self.walker.walk().collect();
 
         let mut fixed = 0;
         let mut failed = 0;
         let mut skipped = 0;
+        let mut fix_errors = 0;
+        let mut too_large = 0;
+        let mut unreadable = Vec::new();
+        let mut suppressed = Vec::new();
+        let mut third_party = Vec::new();
+        let mut foreign_licenses = Vec::new();
+        let mut policy = Vec::new();
+        let mut copyright = Vec::new();
 
         for entry in entries {
-            // Check if file needs fixing
-            let result = self.check_file(&entry)?;
+            // Check if file needs fixing; unreadable paths are reported, not fatal
+            let result = match entry {
+                Ok(entry) => match self.check_file(&entry)? {
+                    Some(result) => result,
+                    // Excluded by a nested config's ignore patterns
+                    None => continue,
+                },
+                Err(e) => match access_error_reason(&e) {
+                    Some(reason) => self.unreadable_result(reason),
+                    None => return Err(e),
+                },
+            };
+
+            if result.status.access_error().is_some() {
+                unreadable.push(result.clone());
+            }
+            if result.status.is_suppressed() {
+                suppressed.push(result.clone());
+            }
+            if result.status.is_third_party() {
+                third_party.push(result.clone());
+            }
+
+            // The policy applies to the license the file keeps
+            let layer = self.hierarchy.layer_for(result.path.as_path())?;
+            let decision = if layer.config.policy.is_empty()
+                || (self.force && result.status.is_foreign_license())
+            {
+                None
+            } else {
+                let content = std::fs::read(result.path.as_path()).unwrap_or_default();
+                let license = layer.file_license(&result.status, &content);
+                layer.policy_result(&result, license.as_deref())
+            };
+            let denied = decision.as_ref().is_some_and(|d| d.verdict == PolicyVerdict::Denied);
+            policy.extend(decision);
 
             match result.status {
-                FileStatus::MissingHeader => match self.fix_file(&result.path) {
+                FileStatus::ForeignLicense { ref detected } if !self.force => {
+                    debug!(
+                        path = %result.path.as_path().display(),
+                        detected = %detected,
+                        "Refusing to overwrite foreign license (use --force)"
+                    );
+                    foreign_licenses.push(result.clone());
+                    failed += 1;
+                }
+                FileStatus::ForeignLicense { .. } => match self.fix_file(&result.path, true) {
+                    Ok(_) => {
+                        debug!(path = %result.path.as_path().display(), "Replaced foreign license");
+                        fixed += 1;
+                    }
+                    Err(e) => {
+                        debug!(path = %result.path.as_path().display(), error = %e, "Failed to fix file");
+                        failed += 1;
+                        fix_errors += 1;
+                    }
+                },
+                FileStatus::MissingHeader => match self.fix_file(&result.path, false) {
                     Ok(_) => {
                         debug!(path = %result.path.as_path().display(), "Fixed file");
                         fixed += 1;
@@ -70,18 +142,36 @@ impl HeaderFixer {
                     Err(e) => {
                         debug!(path = %result.path.as_path().display(), error = %e, "Failed to fix file");
                         failed += 1;
+                        fix_errors += 1;
                     }
                 },
-                FileStatus::HasHeader => {
-                    // Already has header, count as passed
+                FileStatus::HasHeader | FileStatus::ThirdParty { allowed: true, .. } => {
+                    // Already has header or an accepted third-party license, count as passed
+                    // unless the policy denies its license
+                    if denied {
+                        failed += 1;
+                    }
                 }
-                FileStatus::Skipped { reason: _ } => {
+                FileStatus::Skipped { ref reason } => {
+                    if matches!(reason, SkipReason::TooLarge { .. }) {
+                        too_large += 1;
+                    }
                     skipped += 1;
                 }
-                FileStatus::MalformedHeader { .. } => {
+                FileStatus::MalformedHeader { .. }
+                | FileStatus::Unreadable { .. }
+                | FileStatus::ThirdParty { allowed: false, .. } => {
                     failed += 1;
                 }
             }
+
+            // Holder spelling is normalised after any header fix; other holders are reported
+            let flagged = self.fix_copyright(&result)?;
+            if flagged.iter().any(|c| c.issue.is_failure()) && result.status.is_passed() && !denied
+            {
+                failed += 1;
+            }
+            copyright.extend(flagged);
         }
 
         let total = fixed + failed + skipped;
@@ -89,13 +179,22 @@ impl HeaderFixer {
         let summary = ScanSummary::new(
             total, fixed, // Fixed files now pass
             failed, skipped, duration,
-        );
+        )
+        .with_fix_errors(fix_errors)
+        .with_too_large(too_large)
+        .with_unreadable(unreadable)
+        .with_suppressed(suppressed)
+        .with_third_party(third_party)
+        .with_foreign_licenses(foreign_licenses)
+        .with_policy(policy)
+        .with_copyright(copyright);
 
         info!(
             total = summary.total,
             fixed,
             failed = summary.failed,
             skipped = summary.skipped,
+            fix_errors,
             duration = ?duration,
             "Fix operation complete"
         );
@@ -104,67 +203,141 @@ impl HeaderFixer {
     }
 
     /// Checks a single file to determine its header status.
+    ///
+    /// Returns `None` for files excluded by a nested config's ignore patterns.
     #[tracing::instrument(skip(self))]
-    fn check_file(&self, entry: &WalkEntry) -> Result<ScanResult> {
+    fn check_file(&self, entry: &WalkEntry) -> Result<Option<ScanResult>> {
         use crate::scanner::filter::{is_binary, is_valid_utf8};
 
+        // Configuration from the nearest nested .flc.toml, or the root
+        let layer = self.hierarchy.layer_for(&entry.path)?;
+        if layer.is_ignored(&entry.path) {
+            return Ok(None);
+        }
+
         let file_path = FilePath::new(entry.path.clone());
 
+        // Skip oversized files from walker metadata, before reading any content
+        if let Some(reason) = entry.exceeds_size(layer.config.max_file_size) {
+            return Ok(Some(ScanResult {
+                path: file_path,
+                status: FileStatus::Skipped { reason },
+            }));
+        }
+
         // Read file content first
         let content = match std::fs::read(file_path.as_path()) {
             Ok(content) => content,
-            Err(_e) => {
-                // File read error - skip with appropriate reason
-                return Ok(ScanResult {
-                    path: file_path.clone(),
-                    status: FileStatus::Skipped { reason: SkipReason::UnsupportedEncoding },
-                });
+            Err(e) => {
+                let reason = SkipReason::from_io_error(file_path.as_path(), &e);
+                return Ok(Some(self.unreadable_result(reason)));
             }
         };
 
         // Check if binary
         if is_binary(&content) {
-            return Ok(ScanResult {
+            return Ok(Some(ScanResult {
                 path: file_path.clone(),
                 status: FileStatus::Skipped { reason: SkipReason::Binary },
-            });
+            }));
         }
 
         // Check if valid UTF-8 for text processing
         if !is_valid_utf8(&content) {
-            return Ok(ScanResult {
+            return Ok(Some(ScanResult {
                 path: file_path.clone(),
                 status: FileStatus::Skipped { reason: SkipReason::UnsupportedEncoding },
-            });
+            }));
         }
 
         // Check if we should process this file
-        let extension = file_path.extension().map(|ext| ext.as_str().to_string());
-        if let Err(reason) = crate::scanner::filter::should_process_file(
+        if let Err(reason) = crate::scanner::filter::should_process_path(
             &content,
-            extension.as_deref(),
-            &self.config,
+            file_path.as_path(),
+            layer.checker.styles(),
+            &layer.config,
         ) {
-            return Ok(ScanResult {
+            return Ok(Some(ScanResult {
                 path: file_path.clone(),
                 status: FileStatus::Skipped { reason },
-            });
+            }));
+        }
+
+        // Vendored files keep their own license; never insert our header there
+        if let Some(rule) = layer.third_party_rule(file_path.as_path()) {
+            let status = layer.checker.check_third_party(&content, file_path.as_path(), rule);
+            return Ok(Some(ScanResult { path: file_path, status }));
         }
 
         // Check header
-        match self.checker.check_file(file_path.as_path()) {
-            Ok(status) => Ok(ScanResult { path: file_path.clone(), status }),
-            Err(_) => Ok(ScanResult {
+        match layer.checker.check_file(file_path.as_path()) {
+            Ok(status) => Ok(Some(ScanResult { path: file_path.clone(), status })),
+            Err(_) => Ok(Some(ScanResult {
                 path: file_path.clone(),
                 status: FileStatus::Skipped { reason: SkipReason::UnsupportedEncoding },
-            }),
+            })),
+        }
+    }
+
+    /// Normalises copyright holder spellings and, with git history, widens the
+    /// copyright years, then returns the holders still flagged.
+    fn fix_copyright(&self, result: &ScanResult) -> Result<Vec<CopyrightResult>> {
+        use crate::checker::copyright::HolderPolicy;
+        use crate::fixer::copyright::{normalize_holders, update_years};
+        use crate::fixer::writer::write_atomic;
+
+        let layer = self.hierarchy.layer_for(result.path.as_path())?;
+        let holders = HolderPolicy::new(&layer.config.copyright);
+        if !holders.is_active() || result.status.is_skipped() || result.status.is_third_party() {
+            return Ok(Vec::new());
+        }
+
+        let Ok(content) = std::fs::read(result.path.as_path()) else {
+            return Ok(Vec::new());
+        };
+        let history = self.hierarchy.file_history(&layer, result.path.as_path());
+        let flagged = layer.copyright_results(result, &content, history);
+        if !flagged.iter().any(|c| c.issue.is_fixable()) {
+            return Ok(flagged);
+        }
+        let normalized = normalize_holders(&content, &holders);
+        let updated = history
+            .and_then(|history| {
+                update_years(normalized.as_deref().unwrap_or(&content), history, &holders)
+            })
+            .or(normalized);
+        let Some(updated) = updated else {
+            return Ok(flagged);
+        };
+
+        match write_atomic(result.path.as_path(), &updated) {
+            Ok(()) => {
+                debug!(path = %result.path.as_path().display(), "Updated copyright statements");
+                Ok(layer.copyright_results(result, &updated, history))
+            }
+            Err(e) => {
+                debug!(path = %result.path.as_path().display(), error = %e, "Failed to normalise copyright holders");
+                Ok(flagged)
+            }
+        }
+    }
+
+    /// Builds the result for a path that could not be read.
+    fn unreadable_result(&self, reason: SkipReason) -> ScanResult {
+        let path = reason.access_error().map(|(path, _)| path.to_path_buf()).unwrap_or_default();
+        ScanResult {
+            path: FilePath::new(path),
+            status: FileStatus::unreadable(reason, self.config.fail_on_unreadable),
         }
     }
 
     /// Fixes a single file by adding the license header.
+    ///
+    /// With `replace`, the leading comment block (a foreign license) is replaced.
     #[tracing::instrument(skip(self))]
-    fn fix_file(&self, path: &FilePath) -> Result<()> {
-        use crate::fixer::inserter::insert_header;
+    fn fix_file(&self, path: &FilePath, replace: bool) -> Result<()> {
+        use crate::checker::prelude::PreludeLanguage;
+        use crate::fixer::inserter::{insert_header_for, replace_leading_comment};
         use crate::fixer::writer::write_atomic;
 
         // Read the file content
@@ -173,20 +346,24 @@ impl HeaderFixer {
             source,
         })?;
 
-        // Get comment style for this file
-        let extension = path.extension().map(|ext| ext.as_str().to_string()).unwrap_or_default();
-        let style_config = self.config.comment_styles.get(&extension).ok_or_else(|| {
+        // Get comment style for this file (name, glob, extension, then shebang/modeline)
+        let layer = self.hierarchy.layer_for(path.as_path())?;
+        let style = layer.checker.comment_style_for(path.as_path(), &content).ok_or_else(|| {
             FixerError::UnsupportedExtension {
-                extension: extension.to_string(),
+                extension: path.extension().map(|ext| ext.to_string()).unwrap_or_default(),
                 path: path.as_path().to_path_buf(),
             }
         })?;
-        let style = CommentStyle::new(style_config.prefix.clone(), style_config.suffix.clone());
 
         // Insert the header
         use crate::types::header_types::LicenseHeader;
-        let license_header = LicenseHeader::new(self.config.license_header.clone())?;
-        let new_content = insert_header(&content, &license_header, &style)?;
+        let license_header = LicenseHeader::new(layer.config.license_header.clone())?;
+        let language = PreludeLanguage::detect(path.as_path(), &content);
+        let new_content = if replace {
+            replace_leading_comment(&content, &license_header, style, language)?
+        } else {
+            insert_header_for(&content, &license_header, style, language)?
+        };
 
         // Write atomically
         write_atomic(path.as_path(), &new_content)?;
@@ -0,0 +1,237 @@
+//! CycloneDX 1.5 bills of materials from scan evidence.
+//!
+//! Scanned files are grouped into one component per package directory, the
+//! nearest directory with a package manifest (or the scan root). Each file is a
+//! nested `file` component with its SHA-256 hash and the license found in its
+//! header as license evidence.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{creation_time, hex, object};
+use crate::scanner::packages::PACKAGE_MANIFESTS;
+use crate::types::FileEvidence;
+
+/// Render a CycloneDX 1.5 JSON document for the files scanned under `root`
+///
+/// `name` names the top-level component, usually the root directory's name.
+pub fn to_json(name: &str, root: &Path, evidence: &[FileEvidence]) -> Value {
+    let mut packages = PackageDirs::new(root);
+    let mut groups: BTreeMap<PathBuf, Vec<(PathBuf, &FileEvidence)>> = BTreeMap::new();
+    for file in evidence {
+        let path = file.path.as_path().strip_prefix(root).unwrap_or(file.path.as_path());
+        let package = packages.package_dir(path);
+        groups.entry(package).or_default().push((path.to_path_buf(), file));
+    }
+
+    let mut serial = Sha256::new();
+    let components: Vec<Value> = groups
+        .into_iter()
+        .map(|(dir, mut files)| {
+            files.sort_by(|a, b| a.0.cmp(&b.0));
+            for (path, file) in &files {
+                serial.update(path.to_string_lossy().as_bytes());
+                serial.update(file.sha256.as_bytes());
+            }
+            package_component(name, &dir, &files)
+        })
+        .collect();
+
+    let tool = object([
+        ("type", Value::from("application")),
+        ("name", Value::from("flc")),
+        ("version", Value::from(crate::VERSION)),
+    ]);
+    let metadata = object([
+        ("timestamp", Value::from(creation_time())),
+        ("tools", object([("components", Value::Array(vec![tool]))])),
+        (
+            "component",
+            object([
+                ("type", Value::from("application")),
+                ("bom-ref", Value::from("root")),
+                ("name", Value::from(name)),
+            ]),
+        ),
+    ]);
+
+    object([
+        ("bomFormat", Value::from("CycloneDX")),
+        ("specVersion", Value::from("1.5")),
+        ("serialNumber", Value::from(serial_number(&serial.finalize()))),
+        ("version", Value::from(1)),
+        ("metadata", metadata),
+        ("components", Value::Array(components)),
+    ])
+}
+
+/// The component for one package directory, with its files nested
+fn package_component(root_name: &str, dir: &Path, files: &[(PathBuf, &FileEvidence)]) -> Value {
+    let location = slash_path(dir);
+    let name = dir
+        .file_name()
+        .map_or_else(|| root_name.to_string(), |name| name.to_string_lossy().into_owned());
+    let licenses: BTreeSet<&str> =
+        files.iter().filter_map(|(_, file)| file.license.as_deref()).collect();
+
+    let file_components: Vec<Value> = files
+        .iter()
+        .map(|(path, file)| {
+            let path = slash_path(path);
+            let hash = object([
+                ("alg", Value::from("SHA-256")),
+                ("content", Value::from(file.sha256.clone())),
+            ]);
+            let mut component = vec![
+                ("type", Value::from("file")),
+                ("bom-ref", Value::from(format!("file:{}", path))),
+                ("name", Value::from(path)),
+                ("hashes", Value::Array(vec![hash])),
+            ];
+            if let Some(license) = &file.license {
+                component.push((
+                    "evidence",
+                    object([("licenses", Value::Array(vec![license_choice(license)]))]),
+                ));
+            }
+            Value::Object(
+                component.into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+            )
+        })
+        .collect();
+
+    let occurrences: Vec<Value> = files
+        .iter()
+        .map(|(path, _)| object([("location", Value::from(slash_path(path)))]))
+        .collect();
+    let evidence = object([
+        ("licenses", Value::Array(licenses.into_iter().map(license_choice).collect())),
+        ("occurrences", Value::Array(occurrences)),
+    ]);
+
+    object([
+        ("type", Value::from("library")),
+        ("bom-ref", Value::from(format!("package:{}", location))),
+        ("name", Value::from(name)),
+        ("evidence", evidence),
+        ("components", Value::Array(file_components)),
+    ])
+}
+
+/// A CycloneDX license choice: a single SPDX id or an expression
+fn license_choice(license: &str) -> Value {
+    let is_expression = [" AND ", " OR ", " WITH "].iter().any(|op| license.contains(op));
+    if is_expression {
+        object([("expression", Value::from(license))])
+    } else {
+        object([("license", object([("id", Value::from(license))]))])
+    }
+}
+
+/// `urn:uuid:` serial number derived from the file hashes, so it is stable per tree
+fn serial_number(digest: &[u8]) -> String {
+    let hex = hex(digest);
+    let part = |range: std::ops::Range<usize>| hex.get(range).unwrap_or_default().to_string();
+    format!(
+        "urn:uuid:{}-{}-5{}-8{}-{}",
+        part(0..8),
+        part(8..12),
+        part(13..16),
+        part(17..20),
+        part(20..32)
+    )
+}
+
+/// Relative path with `/` separators, `.` for the root
+fn slash_path(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.is_empty() {
+        ".".to_string()
+    } else {
+        path
+    }
+}
+
+/// Finds the package directory of scanned files, caching manifest lookups
+struct PackageDirs<'a> {
+    root: &'a Path,
+    is_package: HashMap<PathBuf, bool>,
+}
+
+impl<'a> PackageDirs<'a> {
+    fn new(root: &'a Path) -> Self {
+        Self { root, is_package: HashMap::new() }
+    }
+
+    /// The nearest directory of `path` (relative to the root) with a manifest
+    fn package_dir(&mut self, path: &Path) -> PathBuf {
+        for dir in path.ancestors().skip(1) {
+            let root = self.root;
+            let is_package = *self.is_package.entry(dir.to_path_buf()).or_insert_with(|| {
+                PACKAGE_MANIFESTS.iter().any(|manifest| root.join(dir).join(manifest).is_file())
+            });
+            if is_package {
+                return dir.to_path_buf();
+            }
+        }
+        PathBuf::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::types::FilePath;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn evidence(root: &Path, path: &str, license: Option<&str>) -> FileEvidence {
+        FileEvidence {
+            path: FilePath::new(root.join(path)),
+            license: license.map(ToString::to_string),
+            sha256: "ab".repeat(32),
+        }
+    }
+
+    #[test]
+    fn groups_files_by_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("crates/core/src")).unwrap();
+        fs::write(root.join("crates/core/Cargo.toml"), "[package]\n").unwrap();
+
+        let files = [
+            evidence(root, "build.rs", None),
+            evidence(root, "crates/core/src/lib.rs", Some("MIT OR Apache-2.0")),
+            evidence(root, "crates/core/src/util.rs", Some("MIT")),
+        ];
+        let bom = to_json("repo", root, &files);
+
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["specVersion"], "1.5");
+        assert!(bom["serialNumber"].as_str().unwrap().starts_with("urn:uuid:"));
+
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 2);
+        let (top, core) = (components.first().unwrap(), components.get(1).unwrap());
+        assert_eq!(top["name"], "repo");
+        assert_eq!(top["bom-ref"], "package:.");
+        assert_eq!(core["name"], "core");
+        assert_eq!(core["bom-ref"], "package:crates/core");
+
+        let licenses = core["evidence"]["licenses"].as_array().unwrap();
+        assert_eq!(licenses.first().unwrap()["license"]["id"], "MIT");
+        assert_eq!(licenses.get(1).unwrap()["expression"], "MIT OR Apache-2.0");
+
+        let file = &core["components"][0];
+        assert_eq!(file["type"], "file");
+        assert_eq!(file["name"], "crates/core/src/lib.rs");
+        assert_eq!(file["hashes"][0]["alg"], "SHA-256");
+        assert_eq!(file["evidence"]["licenses"][0]["expression"], "MIT OR Apache-2.0");
+        assert!(top["components"][0].get("evidence").is_none());
+    }
+}
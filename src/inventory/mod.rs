@@ -0,0 +1,302 @@
+//! License and copyright inventory.
+//!
+//! Walks the tree like a scan and records, for every text file, the license
+//! and copyright statements found in its header together with a checksum, so
+//! the results can be summarised per license and holder, exported as SPDX or
+//! rendered as a third-party notice.
+//! Scan evidence can also be exported as a CycloneDX bill of materials.
+
+pub mod cyclonedx;
+pub mod notice;
+pub mod spdx;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::Gitignore;
+use rayon::iter::ParallelIterator;
+use serde_json::{Map, Value};
+use sha1::{Digest, Sha1};
+
+use crate::checker::copyright::{attribution_statements, copyright_holder, copyright_statements};
+use crate::checker::licenses::identify_license;
+use crate::config::{Config, ThirdPartyRule};
+use crate::error::Result;
+use crate::scanner::filter::is_binary;
+use crate::scanner::hierarchy::{matches_rule, path_rules};
+use crate::scanner::walker::{FileWalker, WalkEntry};
+use crate::types::LicenseClassification;
+
+/// Example paths kept per license and holder
+const MAX_EXAMPLES: usize = 3;
+
+/// License and copyright information of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRecord {
+    /// Path relative to the inventory root
+    pub path: PathBuf,
+    /// License found in the header
+    pub license: LicenseClassification,
+    /// Copyright statements in the header, without comment markers
+    pub copyrights: Vec<String>,
+    /// Attribution lines in the header, e.g. `Originally written by ...`
+    pub attributions: Vec<String>,
+    /// Whether a `[[third_party]]` rule matches the file
+    pub third_party: bool,
+    /// Lowercase hex SHA-1 of the whole file
+    pub sha1: String,
+}
+
+impl FileRecord {
+    /// The license as an SPDX expression, `NOASSERTION` or `NONE`
+    pub fn license_expression(&self) -> String {
+        match &self.license {
+            LicenseClassification::Known { id } => id.clone(),
+            LicenseClassification::Ours | LicenseClassification::Unknown => {
+                "NOASSERTION".to_string()
+            }
+            LicenseClassification::None => "NONE".to_string(),
+        }
+    }
+
+    /// Distinct copyright holders named in the header
+    pub fn holders(&self) -> BTreeSet<String> {
+        self.copyrights.iter().filter_map(|statement| copyright_holder(statement)).collect()
+    }
+}
+
+/// Files sharing one license or copyright holder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryEntry {
+    /// License expression or holder name
+    pub name: String,
+    /// Number of files
+    pub files: usize,
+    /// The first few paths, in path order
+    pub examples: Vec<PathBuf>,
+}
+
+/// Licenses and copyright holders of every file under a root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    /// The directory that was walked
+    pub root: PathBuf,
+    /// One record per text file, sorted by path
+    pub files: Vec<FileRecord>,
+}
+
+impl Inventory {
+    /// Walk `root` with the configured filters and record every text file
+    ///
+    /// Binary, empty and oversized files are left out; unreadable files are
+    /// logged and left out. Files are marked as third-party by the root
+    /// configuration's `[[third_party]]` rules.
+    #[tracing::instrument(skip(config))]
+    pub fn collect(root: &Path, config: &Config) -> Result<Self> {
+        let walker = FileWalker::for_config(root, config)?
+            .with_parallelism(config.parallel_jobs.unwrap_or_else(num_cpus::get));
+        let third_party = path_rules(root, &config.third_party, |rule| &rule.paths)?;
+
+        let mut files: Vec<FileRecord> = walker
+            .walk()
+            .filter_map(|entry| match entry {
+                Ok(entry) => record(root, &entry, config, &third_party),
+                Err(e) => {
+                    tracing::warn!("Error walking directory entry: {}", e);
+                    None
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(Self { root: root.to_path_buf(), files })
+    }
+
+    /// Files matched by `[[third_party]]` rules, in path order
+    pub fn third_party(&self) -> impl Iterator<Item = &FileRecord> {
+        self.files.iter().filter(|file| file.third_party)
+    }
+
+    /// Files per license expression, most common first
+    pub fn licenses(&self) -> Vec<InventoryEntry> {
+        group(self.files.iter().map(|file| (vec![file.license_expression()], &file.path)))
+    }
+
+    /// Files per copyright holder, most common first
+    pub fn holders(&self) -> Vec<InventoryEntry> {
+        group(self.files.iter().map(|file| (file.holders().into_iter().collect(), &file.path)))
+    }
+}
+
+/// Read one walked file into a record
+fn record(
+    root: &Path,
+    entry: &WalkEntry,
+    config: &Config,
+    third_party: &[(Gitignore, ThirdPartyRule)],
+) -> Option<FileRecord> {
+    if entry.exceeds_size(config.max_file_size).is_some() {
+        return None;
+    }
+    let content = match std::fs::read(&entry.path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Could not read {}: {}", entry.path.display(), e);
+            return None;
+        }
+    };
+
+    let header = content.get(..config.max_header_bytes).unwrap_or(&content);
+    if header.is_empty() || is_binary(header) {
+        return None;
+    }
+
+    Some(FileRecord {
+        path: entry.relative_path(root).unwrap_or_else(|| entry.path.clone()),
+        license: identify_license(header),
+        copyrights: copyright_statements(header),
+        attributions: attribution_statements(header),
+        third_party: third_party.iter().any(|(paths, _)| matches_rule(paths, &entry.path)),
+        sha1: hex(&Sha1::digest(&content)),
+    })
+}
+
+/// Count files per name, keeping example paths
+fn group<'a>(items: impl Iterator<Item = (Vec<String>, &'a PathBuf)>) -> Vec<InventoryEntry> {
+    let mut groups: BTreeMap<String, InventoryEntry> = BTreeMap::new();
+    for (names, path) in items {
+        for name in names {
+            let entry = groups.entry(name.clone()).or_insert_with(|| InventoryEntry {
+                name,
+                files: 0,
+                examples: Vec::new(),
+            });
+            entry.files = entry.files.saturating_add(1);
+            if entry.examples.len() < MAX_EXAMPLES {
+                entry.examples.push(path.clone());
+            }
+        }
+    }
+
+    let mut entries: Vec<InventoryEntry> = groups.into_values().collect();
+    entries.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.name.cmp(&b.name)));
+    entries
+}
+
+/// Lowercase hex encoding of a digest
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Document creation time, from `SOURCE_DATE_EPOCH` when set for reproducible output
+pub(crate) fn creation_time() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs())
+        })
+        .unwrap_or(0);
+    format_timestamp(seconds)
+}
+
+/// Build a JSON object from key/value pairs
+pub fn object<const N: usize>(entries: [(&str, Value); N]) -> Value {
+    Value::Object(
+        entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect::<Map<_, _>>(),
+    )
+}
+
+/// Format Unix seconds as an ISO 8601 UTC timestamp
+#[allow(clippy::arithmetic_side_effects)] // Calendar arithmetic on bounded values
+fn format_timestamp(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let time = seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn collects_licenses_and_holders() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+
+        fs::write(
+            root.join("a.rs"),
+            "// Copyright 2024 Acme Corp.\n// SPDX-License-Identifier: Apache-2.0\nfn a() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("b.rs"),
+            "// Copyright 2023 Acme Corp.\n// SPDX-License-Identifier: Apache-2.0\nfn b() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("vendor/c.rs"), "// Copyright (c) 2010 Jane Doe\n// MIT License\n")
+            .unwrap();
+        fs::write(root.join("d.rs"), "fn d() {}\n").unwrap();
+        fs::write(root.join("blob.rs"), b"\x00\x01\x02").unwrap();
+
+        let inventory = Inventory::collect(root, &Config::default()).unwrap();
+
+        let paths: Vec<_> = inventory.files.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("a.rs"),
+                PathBuf::from("b.rs"),
+                PathBuf::from("d.rs"),
+                PathBuf::from("vendor/c.rs")
+            ]
+        );
+
+        let licenses = inventory.licenses();
+        let first = licenses.first().unwrap();
+        assert_eq!((first.name.as_str(), first.files), ("Apache-2.0", 2));
+        assert_eq!(first.examples, vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")]);
+        assert!(licenses.iter().any(|entry| entry.name == "MIT" && entry.files == 1));
+        assert!(licenses.iter().any(|entry| entry.name == "NONE" && entry.files == 1));
+
+        let holders = inventory.holders();
+        assert_eq!(holders.len(), 2);
+        assert_eq!(holders.first().unwrap().name, "Acme Corp");
+        assert_eq!(holders.get(1).unwrap().name, "Jane Doe");
+
+        assert_eq!(inventory.files.first().unwrap().sha1.len(), 40);
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(1_709_251_199), "2024-02-29T23:59:59Z");
+    }
+}
@@ -0,0 +1,251 @@
+//! Third-party notice files.
+//!
+//! Renders the third-party files of an inventory as a `NOTICE` or
+//! `THIRD_PARTY_LICENSES.md`, grouped by license, with the copyright and
+//! attribution lines of each file and the bundled text of each known license.
+//! The output depends only on the tree, so a committed notice can be compared
+//! byte for byte with a fresh one.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::{FileRecord, Inventory};
+use crate::checker::licenses::{known_license, license_ids};
+
+/// Width of the rule lines in the plain text format
+const RULE_WIDTH: usize = 80;
+
+/// Layout of the generated notice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoticeFormat {
+    /// Plain text `NOTICE`
+    Text,
+    /// Markdown `THIRD_PARTY_LICENSES.md`
+    Markdown,
+}
+
+impl NoticeFormat {
+    /// The conventional file name for this format
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Text => "NOTICE",
+            Self::Markdown => "THIRD_PARTY_LICENSES.md",
+        }
+    }
+}
+
+/// Render the notice for the third-party files of `inventory`
+///
+/// `name` names the project in the title, usually from [`project_name`]. It must
+/// not depend on where the tree is checked out, or `flc notice --check` would fail
+/// in a clone under another directory name.
+pub fn render(inventory: &Inventory, name: Option<&str>, format: NoticeFormat) -> String {
+    let groups = license_groups(inventory);
+    let mut out = String::new();
+    match format {
+        NoticeFormat::Text => render_text(&mut out, name, &groups),
+        NoticeFormat::Markdown => render_markdown(&mut out, name, &groups),
+    }
+    out
+}
+
+/// The project name declared by the manifest in `root`, if any
+///
+/// Read from `[package]` in `Cargo.toml`, `"name"` in `package.json`, or
+/// `[project]` / `[tool.poetry]` in `pyproject.toml`, in that order.
+pub fn project_name(root: &Path) -> Option<String> {
+    let read = |name: &str| std::fs::read_to_string(root.join(name)).ok();
+    let toml_name = |content: String, tables: &[&[&str]]| {
+        let manifest = toml::Value::Table(toml::from_str(&content).ok()?);
+        tables.iter().find_map(|keys| {
+            let table = keys.iter().try_fold(&manifest, |value, key| value.get(key))?;
+            Some(table.get("name")?.as_str()?.to_string())
+        })
+    };
+
+    read("Cargo.toml")
+        .and_then(|content| toml_name(content, &[&["package"]]))
+        .or_else(|| {
+            let manifest: serde_json::Value = serde_json::from_str(&read("package.json")?).ok()?;
+            Some(manifest.get("name")?.as_str()?.to_string())
+        })
+        .or_else(|| {
+            read("pyproject.toml")
+                .and_then(|content| toml_name(content, &[&["project"], &["tool", "poetry"]]))
+        })
+}
+
+/// Third-party files per license expression, known licenses first
+fn license_groups(inventory: &Inventory) -> Vec<(String, Vec<&FileRecord>)> {
+    let mut groups: BTreeMap<(bool, String), Vec<&FileRecord>> = BTreeMap::new();
+    for file in inventory.third_party() {
+        let expression = file.license_expression();
+        let unknown = matches!(expression.as_str(), "NOASSERTION" | "NONE");
+        groups.entry((unknown, expression)).or_default().push(file);
+    }
+    groups.into_iter().map(|((_, expression), files)| (expression, files)).collect()
+}
+
+/// Bundled texts of the licenses named in an expression
+fn license_texts(expression: &str) -> Vec<(&'static str, &'static str)> {
+    license_ids(expression)
+        .iter()
+        .filter_map(|id| known_license(id))
+        .map(|license| (license.id, license.text().trim_end()))
+        .collect()
+}
+
+/// The title, naming the project when known
+fn title(heading: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{} for {}", heading, name),
+        None => heading.to_string(),
+    }
+}
+
+fn render_text(out: &mut String, name: Option<&str>, groups: &[(String, Vec<&FileRecord>)]) {
+    let rule = "=".repeat(RULE_WIDTH);
+    let _ = writeln!(out, "{}", title("Third-party notices", name));
+    out.push('\n');
+    out.push_str("Generated by `flc notice` from the headers of third-party files; do not edit.\n");
+    if groups.is_empty() {
+        out.push_str("\nNo third-party files.\n");
+        return;
+    }
+
+    for (expression, files) in groups {
+        let _ = write!(out, "\n{}\n{}\n{}\n", rule, expression, rule);
+        for file in files {
+            let _ = writeln!(out, "\n{}", slash_path(&file.path));
+            for line in file.copyrights.iter().chain(&file.attributions) {
+                let _ = writeln!(out, "    {}", line);
+            }
+        }
+        for (id, text) in license_texts(expression) {
+            let _ = write!(out, "\n--- {} ---\n\n{}\n", id, text);
+        }
+    }
+}
+
+fn render_markdown(out: &mut String, name: Option<&str>, groups: &[(String, Vec<&FileRecord>)]) {
+    let _ = writeln!(out, "# {}", title("Third-party licenses", name));
+    out.push('\n');
+    out.push_str("Generated by `flc notice` from the headers of third-party files; do not edit.\n");
+    if groups.is_empty() {
+        out.push_str("\nNo third-party files.\n");
+        return;
+    }
+
+    for (expression, files) in groups {
+        let _ = writeln!(out, "\n## {}", expression);
+        for file in files {
+            let _ = writeln!(out, "\n### `{}`", slash_path(&file.path));
+            let lines: Vec<&String> = file.copyrights.iter().chain(&file.attributions).collect();
+            if !lines.is_empty() {
+                out.push('\n');
+            }
+            for line in lines {
+                let _ = writeln!(out, "- {}", line);
+            }
+        }
+        for (id, text) in license_texts(expression) {
+            let _ = write!(out, "\n<details>\n<summary>{} license text</summary>\n\n", id);
+            let _ = write!(out, "```text\n{}\n```\n\n</details>\n", text);
+        }
+    }
+}
+
+/// Relative path with `/` separators, the same on every platform
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, ThirdPartyRule};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn groups_third_party_files_by_license() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("vendor/zlib")).unwrap();
+        fs::write(root.join("main.rs"), "// Copyright 2024 Acme Corp\nfn main() {}\n").unwrap();
+        fs::write(
+            root.join("vendor/b.c"),
+            "/* Copyright (c) 2010 Jane Doe\n * SPDX-License-Identifier: MIT\n */\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("vendor/a.c"),
+            "// Copyright 2012 John Roe\n// Originally written by Ann Example\n\
+             // SPDX-License-Identifier: MIT\n",
+        )
+        .unwrap();
+        fs::write(root.join("vendor/zlib/z.c"), "// Copyright 1995 Zed\nint z;\n").unwrap();
+
+        let mut config = Config::default();
+        config.third_party = vec![ThirdPartyRule::new(["vendor/"])];
+        let inventory = Inventory::collect(root, &config).unwrap();
+
+        let text = render(&inventory, Some("repo"), NoticeFormat::Text);
+        assert!(text.starts_with("Third-party notices for repo\n"));
+        assert!(!text.contains("main.rs"));
+        let mit = text.find("\nMIT\n").unwrap();
+        let none = text.find("\nNOASSERTION\n").unwrap();
+        assert!(mit < none);
+        let (a, b) = (text.find("vendor/a.c").unwrap(), text.find("vendor/b.c").unwrap());
+        assert!(mit < a && a < b && b < none);
+        assert!(
+            text.contains("    Copyright 2012 John Roe\n    Originally written by Ann Example\n")
+        );
+        assert!(text.contains("--- MIT ---\n\nPermission is hereby granted"));
+        assert_eq!(text, render(&inventory, Some("repo"), NoticeFormat::Text));
+
+        let markdown = render(&inventory, Some("repo"), NoticeFormat::Markdown);
+        assert!(markdown.contains("## MIT\n\n### `vendor/a.c`\n\n- Copyright 2012 John Roe\n"));
+        assert!(markdown.contains("<summary>MIT license text</summary>"));
+
+        let empty = Inventory::collect(root, &Config::default()).unwrap();
+        assert!(render(&empty, Some("repo"), NoticeFormat::Markdown)
+            .ends_with("No third-party files.\n"));
+    }
+
+    #[test]
+    fn notice_does_not_depend_on_the_checkout_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.third_party = vec![ThirdPartyRule::new(["vendor/"])];
+        let render_in = |dir: &str, manifest: Option<&str>| {
+            let root = temp_dir.path().join(dir);
+            fs::create_dir_all(root.join("vendor")).unwrap();
+            fs::write(root.join("vendor/a.c"), "// SPDX-License-Identifier: MIT\n").unwrap();
+            if let Some(manifest) = manifest {
+                fs::write(root.join("Cargo.toml"), manifest).unwrap();
+            }
+            let inventory = Inventory::collect(&root, &config).unwrap();
+            render(&inventory, project_name(&root).as_deref(), NoticeFormat::Text)
+        };
+
+        let original = render_in("t3", None);
+        assert!(original.starts_with("Third-party notices\n"));
+        assert_eq!(original, render_in("t3clone", None));
+
+        let manifest = "[package]\nname = \"widget\"\n";
+        let named = render_in("w", Some(manifest));
+        assert!(named.starts_with("Third-party notices for widget\n"));
+        assert_eq!(named, render_in("wclone", Some(manifest)));
+    }
+
+    #[test]
+    fn collects_license_texts() {
+        let texts = license_texts("(MIT OR Apache-2.0) AND MIT");
+        let ids: Vec<&str> = texts.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec!["MIT", "Apache-2.0"]);
+        assert!(license_texts("LicenseRef-custom").is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+//! SPDX 2.3 documents from an inventory.
+//!
+//! Each file becomes an SPDX file element with its SHA-1 checksum,
+//! `LicenseInfoInFile` entries from the header license and the header's
+//! copyright statements as `FileCopyrightText`.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+
+use super::{creation_time, object, FileRecord, Inventory};
+use crate::checker::licenses::license_ids;
+
+/// Document-level SPDX fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentInfo {
+    /// Document name
+    pub name: String,
+    /// Unique URI identifying the document
+    pub namespace: String,
+    /// Creation time, `YYYY-MM-DDThh:mm:ssZ`
+    pub created: String,
+}
+
+impl DocumentInfo {
+    /// Document info for `inventory`, created now
+    ///
+    /// `SOURCE_DATE_EPOCH` overrides the creation time for reproducible output.
+    /// The namespace is derived from the name and the file checksums, so the
+    /// same tree always gets the same namespace.
+    pub fn new(name: impl Into<String>, inventory: &Inventory) -> Self {
+        let name = name.into();
+
+        let mut hasher = Sha256::new();
+        hasher.update(name.as_bytes());
+        for file in &inventory.files {
+            hasher.update([0]);
+            hasher.update(file.path.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(file.sha1.as_bytes());
+        }
+        let digest = hasher.finalize();
+        let namespace = format!(
+            "https://spdx.org/spdxdocs/{}-{}",
+            name.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '.', "-"),
+            digest.iter().take(16).map(|byte| format!("{:02x}", byte)).collect::<String>()
+        );
+
+        Self { name, namespace, created: creation_time() }
+    }
+}
+
+/// Render an SPDX 2.3 tag-value document
+pub fn to_tag_value(inventory: &Inventory, info: &DocumentInfo) -> String {
+    let mut out = String::new();
+    // Writing to a String cannot fail
+    let _ = write_tag_value(&mut out, inventory, info);
+    out
+}
+
+fn write_tag_value(
+    out: &mut String,
+    inventory: &Inventory,
+    info: &DocumentInfo,
+) -> std::fmt::Result {
+    writeln!(out, "SPDXVersion: SPDX-2.3")?;
+    writeln!(out, "DataLicense: CC0-1.0")?;
+    writeln!(out, "SPDXID: SPDXRef-DOCUMENT")?;
+    writeln!(out, "DocumentName: {}", info.name)?;
+    writeln!(out, "DocumentNamespace: {}", info.namespace)?;
+    writeln!(out, "Creator: Tool: flc-{}", crate::VERSION)?;
+    writeln!(out, "Created: {}", info.created)?;
+
+    for (index, file) in inventory.files.iter().enumerate() {
+        writeln!(out)?;
+        writeln!(out, "FileName: {}", file_name(file))?;
+        writeln!(out, "SPDXID: {}", file_id(index))?;
+        writeln!(out, "FileChecksum: SHA1: {}", file.sha1)?;
+        writeln!(out, "LicenseConcluded: NOASSERTION")?;
+        for license in license_info(file) {
+            writeln!(out, "LicenseInfoInFile: {}", license)?;
+        }
+        writeln!(out, "FileCopyrightText: {}", copyright_text(file, true))?;
+    }
+
+    if !inventory.files.is_empty() {
+        writeln!(out)?;
+    }
+    for index in 0..inventory.files.len() {
+        writeln!(out, "Relationship: SPDXRef-DOCUMENT DESCRIBES {}", file_id(index))?;
+    }
+    Ok(())
+}
+
+/// Render an SPDX 2.3 JSON document
+pub fn to_json(inventory: &Inventory, info: &DocumentInfo) -> Value {
+    let files: Vec<Value> = inventory
+        .files
+        .iter()
+        .enumerate()
+        .map(|(index, file)| {
+            let checksum = object([
+                ("algorithm", Value::from("SHA1")),
+                ("checksumValue", Value::from(file.sha1.clone())),
+            ]);
+            object([
+                ("fileName", Value::from(file_name(file))),
+                ("SPDXID", Value::from(file_id(index))),
+                ("checksums", Value::Array(vec![checksum])),
+                ("licenseConcluded", Value::from("NOASSERTION")),
+                (
+                    "licenseInfoInFiles",
+                    Value::Array(license_info(file).into_iter().map(Value::from).collect()),
+                ),
+                ("copyrightText", Value::from(copyright_text(file, false))),
+            ])
+        })
+        .collect();
+
+    let relationships: Vec<Value> = (0..inventory.files.len())
+        .map(|index| {
+            object([
+                ("spdxElementId", Value::from("SPDXRef-DOCUMENT")),
+                ("relationshipType", Value::from("DESCRIBES")),
+                ("relatedSpdxElement", Value::from(file_id(index))),
+            ])
+        })
+        .collect();
+
+    let creation_info = object([
+        ("created", Value::from(info.created.clone())),
+        ("creators", Value::Array(vec![Value::from(format!("Tool: flc-{}", crate::VERSION))])),
+    ]);
+
+    object([
+        ("spdxVersion", Value::from("SPDX-2.3")),
+        ("dataLicense", Value::from("CC0-1.0")),
+        ("SPDXID", Value::from("SPDXRef-DOCUMENT")),
+        ("name", Value::from(info.name.clone())),
+        ("documentNamespace", Value::from(info.namespace.clone())),
+        ("creationInfo", creation_info),
+        ("files", Value::Array(files)),
+        ("relationships", Value::Array(relationships)),
+    ])
+}
+
+fn file_id(index: usize) -> String {
+    format!("SPDXRef-File-{}", index.saturating_add(1))
+}
+
+/// SPDX file names are relative paths starting with `./`, using `/`
+fn file_name(file: &FileRecord) -> String {
+    let path = file.path.to_string_lossy().replace('\\', "/");
+    format!("./{}", path.trim_start_matches("./"))
+}
+
+/// License identifiers in the file's expression, without operators or exceptions
+fn license_info(file: &FileRecord) -> Vec<String> {
+    license_ids(&file.license_expression())
+}
+
+/// Copyright statements, one per line, or `NONE`
+fn copyright_text(file: &FileRecord, tagged: bool) -> String {
+    if file.copyrights.is_empty() {
+        return "NONE".to_string();
+    }
+    let text = file.copyrights.join("\n");
+    if tagged {
+        format!("<text>{}</text>", text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::types::LicenseClassification;
+    use std::path::PathBuf;
+
+    fn inventory() -> Inventory {
+        Inventory {
+            root: PathBuf::from("/repo"),
+            files: vec![
+                FileRecord {
+                    path: PathBuf::from("src/lib.rs"),
+                    license: LicenseClassification::Known { id: "MIT OR Apache-2.0".to_string() },
+                    copyrights: vec!["Copyright 2024 Acme Corp.".to_string()],
+                    attributions: Vec::new(),
+                    third_party: false,
+                    sha1: "a".repeat(40),
+                },
+                FileRecord {
+                    path: PathBuf::from("build.rs"),
+                    license: LicenseClassification::None,
+                    copyrights: Vec::new(),
+                    attributions: Vec::new(),
+                    third_party: false,
+                    sha1: "b".repeat(40),
+                },
+            ],
+        }
+    }
+
+    fn info() -> DocumentInfo {
+        DocumentInfo {
+            name: "repo".to_string(),
+            namespace: "https://spdx.org/spdxdocs/repo-0".to_string(),
+            created: "2024-01-02T03:04:05Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn tag_value_document() {
+        let document = to_tag_value(&inventory(), &info());
+
+        assert!(document.starts_with("SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\n"));
+        assert!(document
+            .contains("FileName: ./src/lib.rs\nSPDXID: SPDXRef-File-1\nFileChecksum: SHA1: aaaa"));
+        assert!(document.contains("LicenseInfoInFile: MIT\nLicenseInfoInFile: Apache-2.0\n"));
+        assert!(document.contains("FileCopyrightText: <text>Copyright 2024 Acme Corp.</text>"));
+        assert!(document.contains("LicenseInfoInFile: NONE\nFileCopyrightText: NONE\n"));
+        assert!(document.contains("Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-File-2\n"));
+    }
+
+    #[test]
+    fn json_document() {
+        let document = to_json(&inventory(), &info());
+
+        assert_eq!(document["spdxVersion"], "SPDX-2.3");
+        let file = &document["files"][0];
+        assert_eq!(file["fileName"], "./src/lib.rs");
+        assert_eq!(file["checksums"][0]["algorithm"], "SHA1");
+        assert_eq!(file["licenseInfoInFiles"][1], "Apache-2.0");
+        assert_eq!(file["copyrightText"], "Copyright 2024 Acme Corp.");
+        assert_eq!(document["relationships"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn exceptions_are_not_licenses() {
+        let mut file = inventory().files.remove(0);
+        file.license = LicenseClassification::Known {
+            id: "(GPL-2.0-only WITH Classpath-exception-2.0) AND MIT".to_string(),
+        };
+        assert_eq!(license_info(&file), vec!["GPL-2.0-only", "MIT"]);
+    }
+
+    #[test]
+    fn document_namespace_is_stable() {
+        let namespace = DocumentInfo::new("my repo", &inventory()).namespace;
+        // Pinned so that a toolchain or dependency update cannot change it
+        assert_eq!(namespace, "https://spdx.org/spdxdocs/my-repo-7b5f959606020eed1f9b6569990ce628");
+        assert_ne!(DocumentInfo::new("other", &inventory()).namespace, namespace);
+    }
+}
@@ -9,7 +9,7 @@
 //! - **Fast**: Scans 100,000 files in under 1 second using parallel processing
 //! - **Git-aware**: Automatically respects `.gitignore` files
 //! - **Safe**: Never corrupts binary files
-//! - **Smart**: Handles shebangs, XML declarations, and various comment styles
+//! - **Smart**: Handles shebangs, XML declarations, language preludes, and various comment styles
 //!
 //! ## Architecture
 //!
@@ -20,6 +20,7 @@
 //! - `scanner` - File walking with `.gitignore` support
 //! - `checker` - License header detection and validation
 //! - `fixer` - License header insertion with atomic writes
+//! - `inventory` - License and copyright inventory with SPDX, CycloneDX and NOTICE output
 //! - `error` - Typed error definitions
 //!
 //! ## Example
@@ -41,6 +42,7 @@ pub mod checker;
 pub mod config;
 pub mod error;
 pub mod fixer;
+pub mod inventory;
 pub mod scanner;
 pub mod types;
 
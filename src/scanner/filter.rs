@@ -3,8 +3,13 @@
 //! Provides utilities for detecting binary files, validating encodings,
 //! and determining if files should be skipped during scanning.
 
+use std::path::Path;
+
+use crate::checker::prelude::{effective_header_start_for, PreludeLanguage};
+use crate::checker::styles::CommentStyleResolver;
 use crate::config::Config;
-use crate::types::SkipReason;
+use crate::error::ConfigError;
+use crate::types::{CommentStyle, SkipReason};
 
 /// Detect if content is likely binary (contains NULL bytes)
 #[tracing::instrument(skip(content))]
@@ -41,6 +46,95 @@ pub fn should_skip(content: &[u8], config: &Config) -> Option<SkipReason> {
     None
 }
 
+/// In-file markers that exclude a file from header checks and fixes
+pub const SUPPRESSION_MARKERS: [&str; 2] = ["flc:ignore-file", "flc:disable"];
+
+/// Find a suppression marker in the leading comments of a file
+///
+/// Only whole-line comments and the lines of block comments in `style` before
+/// the first line of code are searched, so marker strings in code, string
+/// literals or doc comments further down don't count. Text
+/// after the marker on the same line (minus a leading `:` or `-` and a
+/// trailing comment closer) is the justification, e.g.
+/// `/* flc:ignore-file: generated by protoc */`.
+#[tracing::instrument(skip(content))]
+pub fn find_suppression(content: &[u8], style: &CommentStyle) -> Option<SkipReason> {
+    let text = String::from_utf8_lossy(content);
+    let mut lines = comment_lines(&text, style);
+    lines.find_map(|line| {
+        SUPPRESSION_MARKERS.iter().find_map(|marker| {
+            line.match_indices(marker).find_map(|(start, _)| {
+                let rest = line.get(start.saturating_add(marker.len())..)?;
+                // `flc:disabled` or `flc:disable-next` are not markers
+                if rest.starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_') {
+                    return None;
+                }
+                let justification = rest
+                    .trim_start_matches([':', '-', ' ', '\t'])
+                    .trim_end()
+                    .trim_end_matches("*/")
+                    .trim_end_matches("-->")
+                    .trim();
+                Some(SkipReason::Suppressed {
+                    marker: (*marker).to_string(),
+                    justification: (!justification.is_empty()).then(|| justification.to_string()),
+                })
+            })
+        })
+    })
+}
+
+/// Comment lines of `text` up to its first line of code
+///
+/// Yields line comments and the lines inside block comments; blank lines
+/// between comments are skipped.
+fn comment_lines<'a>(text: &'a str, style: &'a CommentStyle) -> impl Iterator<Item = &'a str> {
+    let block = style.block_delimiters();
+    let close = block.map_or("", |(_, close)| close);
+    let line_prefix = style.prefix.trim();
+    let mut in_block = false;
+    text.lines()
+        .map(move |line| {
+            let trimmed = line.trim();
+            if in_block {
+                in_block = !trimmed.contains(close);
+                Some(Some(line))
+            } else if trimmed.is_empty() {
+                Some(None)
+            } else if let Some(rest) = block.and_then(|(open, _)| trimmed.strip_prefix(open)) {
+                in_block = !rest.contains(close);
+                Some(Some(line))
+            } else if !line_prefix.is_empty() && trimmed.starts_with(line_prefix) {
+                Some(Some(line))
+            } else {
+                None
+            }
+        })
+        .map_while(|line| line)
+        .flatten()
+}
+
+/// Suppression marker honoured under the configuration, searched in the
+/// leading comments after the prelude within the first `max_header_bytes`
+fn suppression(
+    content: &[u8],
+    path: &Path,
+    style: &CommentStyle,
+    config: &Config,
+) -> Option<SkipReason> {
+    let header = content.get(..config.max_header_bytes).unwrap_or(content);
+    let start = effective_header_start_for(header, PreludeLanguage::detect(path, content));
+    match find_suppression(header.get(start..).unwrap_or_default(), style)? {
+        SkipReason::Suppressed { marker, justification: None }
+            if config.require_suppression_reason =>
+        {
+            tracing::warn!("Ignoring {} without a justification in {}", marker, path.display());
+            None
+        }
+        reason => Some(reason),
+    }
+}
+
 /// Check if a file extension has a configured comment style
 #[tracing::instrument]
 pub fn has_comment_style(config: &Config, extension: Option<&str>) -> bool {
@@ -77,6 +171,96 @@ pub fn should_process_file(
     Ok(())
 }
 
+/// File filtering that resolves comment styles by file name, glob and extension
+#[tracing::instrument(skip(content, styles, config))]
+pub fn should_process_path(
+    content: &[u8],
+    path: &Path,
+    styles: &CommentStyleResolver,
+    config: &Config,
+) -> Result<(), SkipReason> {
+    // First check content-based filters
+    if let Some(reason) = should_skip(content, config) {
+        return Err(reason);
+    }
+
+    // Then check that some comment style applies to this path or its content
+    let Some(style) = styles.resolve_for_content(path, content) else {
+        return Err(SkipReason::NoCommentStyle);
+    };
+
+    // Honour in-file suppression markers in the header comments
+    if let Some(reason) = suppression(content, path, style, config) {
+        return Err(reason);
+    }
+
+    Ok(())
+}
+
+/// File name globs for a language name used by `only_languages`
+///
+/// Languages are selected by extension and well-known file names only;
+/// extensionless scripts are not matched by their shebang.
+pub fn language_globs(language: &str) -> Option<&'static [&'static str]> {
+    let globs: &'static [&'static str] = match language.to_lowercase().as_str() {
+        "rust" => &["*.rs"],
+        "python" => &["*.py", "*.pyi"],
+        "javascript" => &["*.js", "*.jsx", "*.mjs", "*.cjs"],
+        "typescript" => &["*.ts", "*.tsx", "*.mts", "*.cts"],
+        "go" => &["*.go"],
+        "java" => &["*.java"],
+        "kotlin" => &["*.kt", "*.kts"],
+        "scala" => &["*.scala"],
+        "swift" => &["*.swift"],
+        "c" => &["*.c", "*.h"],
+        "cpp" | "c++" => &["*.cpp", "*.cc", "*.cxx", "*.hpp", "*.hxx", "*.h"],
+        "csharp" | "c#" => &["*.cs"],
+        "ruby" => &["*.rb", "Gemfile", "Rakefile", "Vagrantfile"],
+        "shell" | "sh" | "bash" => &["*.sh", "*.bash", "*.zsh"],
+        "perl" => &["*.pl", "*.pm"],
+        "lua" => &["*.lua"],
+        "php" => &["*.php"],
+        "html" => &["*.html", "*.htm"],
+        "xml" => &["*.xml", "*.xsd", "*.svg"],
+        "css" => &["*.css", "*.scss", "*.sass", "*.less"],
+        "yaml" => &["*.yaml", "*.yml"],
+        "toml" => &["*.toml"],
+        "markdown" => &["*.md", "*.markdown"],
+        "sql" => &["*.sql"],
+        "haskell" => &["*.hs", "*.lhs"],
+        "erlang" => &["*.erl", "*.hrl"],
+        "elixir" => &["*.ex", "*.exs"],
+        "dart" => &["*.dart"],
+        "dockerfile" | "docker" => &["Dockerfile", "Containerfile", "*.dockerfile"],
+        "makefile" | "make" => &["Makefile", "GNUmakefile", "*.mk"],
+        "cmake" => &["CMakeLists.txt", "*.cmake"],
+        "bazel" | "starlark" => &["BUILD", "BUILD.bazel", "WORKSPACE", "*.bzl"],
+        _ => return None,
+    };
+    Some(globs)
+}
+
+/// File name globs selected by `only_extensions` and `only_languages`
+///
+/// Returns an empty list when neither filter is set (all files are walked).
+pub fn file_type_globs(config: &Config) -> crate::error::Result<Vec<String>> {
+    let mut globs: Vec<String> = config
+        .only_extensions
+        .iter()
+        .map(|ext| format!("*.{}", ext.trim_start_matches('.')))
+        .collect();
+
+    for language in &config.only_languages {
+        let language_globs = language_globs(language).ok_or_else(|| ConfigError::InvalidValue {
+            field: "only_languages",
+            message: format!("unknown language '{}'", language),
+        })?;
+        globs.extend(language_globs.iter().map(|glob| glob.to_string()));
+    }
+
+    Ok(globs)
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -235,4 +419,118 @@ mod tests {
         let result = should_process_file(content, Some("xyz"), &config);
         assert_eq!(result, Err(SkipReason::NoCommentStyle));
     }
+
+    #[test]
+    fn find_suppression_markers() {
+        let c = CommentStyle::c_style();
+        let reason = find_suppression(b"/* flc:ignore-file: generated by protoc */\nint x;\n", &c);
+        assert_eq!(
+            reason,
+            Some(SkipReason::Suppressed {
+                marker: "flc:ignore-file".to_string(),
+                justification: Some("generated by protoc".to_string()),
+            })
+        );
+
+        let shell = CommentStyle::shell_style();
+        let reason = find_suppression(b"#!/bin/sh\n# flc:disable\necho hi\n", &shell);
+        assert_eq!(
+            reason,
+            Some(SkipReason::Suppressed { marker: "flc:disable".to_string(), justification: None })
+        );
+
+        let rust = CommentStyle::rust_style();
+        assert!(find_suppression(b"// flc:disabled by default\n", &rust).is_none());
+        assert!(find_suppression(b"fn main() {}\n", &rust).is_none());
+    }
+
+    #[test]
+    fn find_suppression_only_in_comments() {
+        // Marker strings in code or string literals don't suppress the file
+        let rust = CommentStyle::rust_style();
+        let code = b"pub const MARKERS: [&str; 2] = [\"flc:ignore-file\", \"flc:disable\"];\n";
+        assert!(find_suppression(code, &rust).is_none());
+        assert!(find_suppression(b"let s = \"# flc:disable\";\n", &rust).is_none());
+
+        // Markers on a continuation line of a block comment count
+        let javadoc = CommentStyle::javadoc_style();
+        let content = b"/*\n * flc:ignore-file generated\n */\nclass A {}\n";
+        assert!(find_suppression(content, &javadoc).is_some());
+        assert!(find_suppression(b"class A {}\n// flc:disable\n", &javadoc).is_none());
+
+        // Comments after the first line of code are not part of the header
+        let content = b"//! Filters\n\nuse std::path::Path;\n\n/// e.g. `// flc:disable`\n";
+        assert!(find_suppression(content, &rust).is_none());
+        let content = b"// Copyright\n\n// flc:disable vendored\nfn lib() {}\n";
+        assert!(find_suppression(content, &rust).is_some());
+    }
+
+    #[test]
+    fn should_process_path_honours_suppressions() {
+        let config = Config::default();
+        let styles = CommentStyleResolver::new(&config).unwrap();
+        let path = Path::new("vendor/lib.rs");
+
+        let content = b"// flc:ignore-file vendored from upstream\nfn lib() {}\n";
+        assert!(matches!(
+            should_process_path(content, path, &styles, &config),
+            Err(SkipReason::Suppressed { .. })
+        ));
+
+        // Bare markers don't count when a justification is required
+        let config = config.with_require_suppression_reason(true);
+        assert!(should_process_path(content, path, &styles, &config).is_err());
+        assert!(should_process_path(b"// flc:ignore-file\nfn lib() {}\n", path, &styles, &config)
+            .is_ok());
+
+        // Markers past the header region are not honoured
+        let config = Config::default().with_max_header_bytes(256);
+        let mut late = "fn lib() {}\n".repeat(40).into_bytes();
+        late.extend_from_slice(b"// flc:ignore-file late\n");
+        assert!(should_process_path(&late, path, &styles, &config).is_ok());
+
+        // A file that merely defines the markers is still checked
+        let source = b"//! Suppression markers\n\nconst M: &str = \"flc:ignore-file\";\n";
+        assert!(should_process_path(source, path, &styles, &Config::default()).is_ok());
+    }
+
+    #[test]
+    fn should_process_path_by_file_name() {
+        let config = Config::default();
+        let styles = CommentStyleResolver::new(&config).unwrap();
+        let content = b"FROM alpine";
+
+        assert!(should_process_path(content, Path::new("Dockerfile"), &styles, &config).is_ok());
+        assert_eq!(
+            should_process_path(content, Path::new("NOTES"), &styles, &config),
+            Err(SkipReason::NoCommentStyle)
+        );
+    }
+
+    #[test]
+    fn should_process_path_by_shebang() {
+        let config = Config::default();
+        let styles = CommentStyleResolver::new(&config).unwrap();
+
+        let script = b"#!/usr/bin/env python3\nprint('deploy')\n";
+        assert!(should_process_path(script, Path::new("bin/deploy"), &styles, &config).is_ok());
+        assert_eq!(
+            should_process_path(b"deploy", Path::new("bin/deploy"), &styles, &config),
+            Err(SkipReason::NoCommentStyle)
+        );
+    }
+
+    #[test]
+    fn file_type_globs_from_extensions_and_languages() {
+        let mut config = Config::default();
+        assert!(file_type_globs(&config).unwrap().is_empty());
+
+        config.only_extensions = vec!["rs".to_string(), ".toml".to_string()];
+        config.only_languages = vec!["Python".to_string()];
+        let globs = file_type_globs(&config).unwrap();
+        assert_eq!(globs, vec!["*.rs", "*.toml", "*.py", "*.pyi"]);
+
+        config.only_languages = vec!["klingon".to_string()];
+        assert!(file_type_globs(&config).is_err());
+    }
 }
@@ -0,0 +1,380 @@
+//! Per-directory configuration layers.
+//!
+//! Like `.gitignore`, a `.flc.toml` file in a subdirectory applies to its whole
+//! subtree: it can override the license header, comment styles and ignore
+//! patterns on top of the configuration inherited from its parent directory.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use ignore::gitignore::Gitignore;
+use serde::de::DeserializeOwned;
+
+use crate::checker::copyright::{copyrights, HolderPolicy};
+use crate::checker::history::{FileHistory, GitHistory};
+use crate::checker::licenses::identify_license;
+use crate::checker::policy::{self, PolicyLists};
+use crate::checker::HeaderChecker;
+use crate::config::loader::read_config_table;
+use crate::config::{overlay_config, Config, PolicyPathRule, ThirdPartyRule, NESTED_CONFIG_FILE};
+use crate::error::{ConfigError, Result};
+use crate::types::{
+    CopyrightIssue, CopyrightResult, FileStatus, LicenseClassification, PolicyResult, ScanResult,
+};
+
+use super::walker::build_ignore_matcher;
+
+/// Effective configuration for one directory
+#[derive(Debug)]
+pub struct ConfigLayer {
+    /// Configuration after applying every nested config file above the directory
+    pub config: Config,
+    /// Header checker built from `config`
+    pub checker: HeaderChecker,
+    /// Ignore patterns from nested config files, each rooted at its own directory
+    ignores: Vec<Gitignore>,
+    /// `[[third_party]]` rules, rooted at the directory of the config that declared them
+    third_party: Vec<(Gitignore, ThirdPartyRule)>,
+    /// `[[policy.paths]]` rules, rooted like `third_party`
+    policy_paths: Vec<(Gitignore, PolicyPathRule)>,
+}
+
+impl ConfigLayer {
+    /// Create the layer for the scan root
+    pub fn new(config: Config, root: &Path) -> Result<Self> {
+        let checker = HeaderChecker::new(&config)?;
+        let third_party = path_rules(root, &config.third_party, |rule| &rule.paths)?;
+        let policy_paths = path_rules(root, &config.policy.paths, |rule| &rule.paths)?;
+        Ok(Self { config, checker, ignores: Vec::new(), third_party, policy_paths })
+    }
+
+    /// Check whether a nested config's ignore patterns exclude `path`
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.ignores.iter().any(|ignores| matches_rule(ignores, path))
+    }
+
+    /// Returns the first `[[third_party]]` rule matching `path`, if any
+    pub fn third_party_rule(&self, path: &Path) -> Option<&ThirdPartyRule> {
+        self.third_party.iter().find(|(paths, _)| matches_rule(paths, path)).map(|(_, rule)| rule)
+    }
+
+    /// Applies the license policy to a checked file
+    ///
+    /// Returns `None` without a policy or when the file has no recognised license.
+    pub fn policy_result(
+        &self,
+        result: &ScanResult,
+        license: Option<&str>,
+    ) -> Option<PolicyResult> {
+        if self.config.policy.is_empty() {
+            return None;
+        }
+        let license = license?.to_string();
+
+        // Matching path rules, in order, before the global lists
+        let scopes: Vec<PolicyLists<'_>> = self
+            .policy_paths
+            .iter()
+            .filter(|(paths, _)| matches_rule(paths, result.path.as_path()))
+            .map(|(_, rule)| PolicyLists::from(rule))
+            .chain(std::iter::once(PolicyLists::from(&self.config.policy)))
+            .collect();
+
+        let verdict = policy::evaluate(&license, &scopes);
+        Some(PolicyResult { path: result.path.clone(), license, verdict })
+    }
+
+    /// Checks the copyright holders in a checked file's header against `[copyright]`
+    ///
+    /// Skipped and third-party files are not checked; their holders are not ours to
+    /// choose. With `history`, the statements are also compared with the file's commits.
+    pub fn copyright_results(
+        &self,
+        result: &ScanResult,
+        content: &[u8],
+        history: Option<&FileHistory>,
+    ) -> Vec<CopyrightResult> {
+        let holders = HolderPolicy::new(&self.config.copyright);
+        if !holders.is_active() || result.status.is_skipped() || result.status.is_third_party() {
+            return Vec::new();
+        }
+        let copyrights = copyrights(content);
+        let mut issues: Vec<(String, CopyrightIssue)> = copyrights
+            .iter()
+            .filter_map(|copyright| {
+                Some((copyright.holder.clone(), holders.check(&copyright.holder)?))
+            })
+            .collect();
+        if let Some(history) = history {
+            issues.extend(holders.check_history(&copyrights, history));
+        }
+        issues
+            .into_iter()
+            .map(|(holder, issue)| CopyrightResult { path: result.path.clone(), holder, issue })
+            .collect()
+    }
+
+    /// The SPDX license a checked file carries, if recognised
+    ///
+    /// A file with our header carries the license identified in that header, or the
+    /// expected header's license when the header names none.
+    pub fn file_license(&self, status: &FileStatus, content: &[u8]) -> Option<String> {
+        let own = || match identify_license(content) {
+            LicenseClassification::Known { id } => Some(id),
+            _ => match self.checker.own_license() {
+                LicenseClassification::Known { id } => Some(id.clone()),
+                _ => None,
+            },
+        };
+        match status {
+            FileStatus::HasHeader => own(),
+            FileStatus::ForeignLicense { detected } => Some(detected.clone()),
+            FileStatus::ThirdParty { license: LicenseClassification::Known { id }, .. } => {
+                Some(id.clone())
+            }
+            FileStatus::ThirdParty { license: LicenseClassification::Ours, .. } => own(),
+            _ => None,
+        }
+    }
+
+    /// Derive the layer for `dir` from its parent layer and the nested config file
+    fn child(parent: &ConfigLayer, dir: &Path, config_file: &Path) -> Result<Self> {
+        let overlay = read_config_table(config_file)?;
+        let own_ignores: Vec<String> = own_entries(&overlay, "ignore_patterns")?;
+        let own_third_party: Vec<ThirdPartyRule> = own_entries(&overlay, "third_party")?;
+        // `[policy]` merges entry by entry, so its own `paths` replace the inherited ones
+        let has_policy_paths = overlay
+            .get("policy")
+            .and_then(toml::Value::as_table)
+            .is_some_and(|policy| policy.contains_key("paths"));
+        let config = overlay_config(&parent.config, overlay)?;
+
+        // A nested config's patterns and rules are relative to its own directory;
+        // the inherited ones stay rooted where they were declared
+        let mut ignores = parent.ignores.clone();
+        if !own_ignores.is_empty() {
+            ignores.push(build_ignore_matcher(dir, &own_ignores)?);
+        }
+        let mut third_party = parent.third_party.clone();
+        third_party.extend(path_rules(dir, &own_third_party, |rule| &rule.paths)?);
+        let policy_paths = if has_policy_paths {
+            path_rules(dir, &config.policy.paths, |rule| &rule.paths)?
+        } else {
+            parent.policy_paths.clone()
+        };
+
+        let checker = HeaderChecker::new(&config)?;
+        Ok(Self { config, checker, ignores, third_party, policy_paths })
+    }
+}
+
+/// The entries a nested config file itself lists under `key`, without inherited ones
+fn own_entries<T: DeserializeOwned>(overlay: &toml::Table, key: &str) -> Result<Vec<T>> {
+    match overlay.get(key) {
+        Some(value) => Ok(value.clone().try_into().map_err(ConfigError::Parse)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Build a path matcher for each rule, rooted at `dir`
+pub(crate) fn path_rules<R: Clone>(
+    dir: &Path,
+    rules: &[R],
+    paths: impl Fn(&R) -> &Vec<String>,
+) -> Result<Vec<(Gitignore, R)>> {
+    rules.iter().map(|rule| Ok((build_ignore_matcher(dir, paths(rule))?, rule.clone()))).collect()
+}
+
+/// Check whether a rule's patterns match `path` (or one of its parents)
+///
+/// The matcher drops a leading `./` from its root, so `path` is compared without
+/// one too; otherwise nothing would match when scanning a relative root like `.`.
+pub(crate) fn matches_rule(paths: &Gitignore, path: &Path) -> bool {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.strip_prefix(paths.path()).is_ok()
+        && paths.matched_path_or_any_parents(path, false).is_ignore()
+}
+
+/// Resolves the configuration layer for any path below the scan root
+///
+/// Layers are discovered lazily and cached per directory, so each nested
+/// config file is read once even when files are checked in parallel.
+#[derive(Debug)]
+pub struct ConfigHierarchy {
+    root: PathBuf,
+    base: Arc<ConfigLayer>,
+    layers: RwLock<HashMap<PathBuf, Arc<ConfigLayer>>>,
+    /// Git history of the root, read on first use
+    history: OnceLock<Option<GitHistory>>,
+}
+
+impl ConfigHierarchy {
+    /// Create a hierarchy whose root layer uses `config`
+    ///
+    /// A `.flc.toml` in `root` itself is not read; `config` takes its place.
+    pub fn new(root: impl AsRef<Path>, config: Config) -> Result<Self> {
+        Ok(Self {
+            root: root.as_ref().to_path_buf(),
+            base: Arc::new(ConfigLayer::new(config, root.as_ref())?),
+            layers: RwLock::new(HashMap::new()),
+            history: OnceLock::new(),
+        })
+    }
+
+    /// The git history of `file`, if its layer compares statements with history
+    ///
+    /// The history of the whole root is read once, when first needed.
+    pub fn file_history(&self, layer: &ConfigLayer, file: &Path) -> Option<&FileHistory> {
+        if !layer.config.copyright.git_history {
+            return None;
+        }
+        self.history.get_or_init(|| GitHistory::load(&self.root)).as_ref()?.file(file)
+    }
+
+    /// The root layer, built from the loaded configuration
+    pub fn base(&self) -> &ConfigLayer {
+        &self.base
+    }
+
+    /// Returns the layer that applies to a file
+    pub fn layer_for(&self, file: &Path) -> Result<Arc<ConfigLayer>> {
+        match file.parent() {
+            Some(dir) => self.layer_for_dir(dir),
+            None => Ok(Arc::clone(&self.base)),
+        }
+    }
+
+    fn layer_for_dir(&self, dir: &Path) -> Result<Arc<ConfigLayer>> {
+        // The root's own config file is the loaded configuration: discovery finds it
+        // first, and an explicit `--config` replaces it rather than layering under it
+        if dir == self.root || !dir.starts_with(&self.root) {
+            return Ok(Arc::clone(&self.base));
+        }
+
+        if let Some(layer) = self.layers.read().ok().and_then(|layers| layers.get(dir).cloned()) {
+            return Ok(layer);
+        }
+
+        let parent = match dir.parent() {
+            Some(parent) => self.layer_for_dir(parent)?,
+            None => Arc::clone(&self.base),
+        };
+
+        let config_file = dir.join(NESTED_CONFIG_FILE);
+        let layer = if config_file.is_file() {
+            tracing::debug!(path = %config_file.display(), "Applying nested configuration");
+            Arc::new(ConfigLayer::child(&parent, dir, &config_file)?)
+        } else {
+            parent
+        };
+
+        if let Ok(mut layers) = self.layers.write() {
+            layers.insert(dir.to_path_buf(), Arc::clone(&layer));
+        }
+        Ok(layer)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn base_config() -> Config {
+        Config::default().with_license_header("MIT License\nCopyright 2024")
+    }
+
+    #[test]
+    fn layer_without_nested_config_is_base() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+
+        let hierarchy = ConfigHierarchy::new(temp_dir.path(), base_config()).unwrap();
+        let layer = hierarchy.layer_for(&temp_dir.path().join("src/main.rs")).unwrap();
+
+        assert_eq!(layer.config.license_header, "MIT License\nCopyright 2024");
+        assert!(!layer.is_ignored(&temp_dir.path().join("src/main.rs")));
+    }
+
+    #[test]
+    fn nested_config_applies_to_subtree() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendor = temp_dir.path().join("vendor");
+        fs::create_dir_all(vendor.join("lib")).unwrap();
+        fs::write(
+            vendor.join(NESTED_CONFIG_FILE),
+            "license_header = \"Apache License\\nCopyright 2020 Vendor\"\n\
+             ignore_patterns = [\"*.gen.rs\"]\n",
+        )
+        .unwrap();
+
+        let hierarchy = ConfigHierarchy::new(temp_dir.path(), base_config()).unwrap();
+
+        let nested = hierarchy.layer_for(&vendor.join("lib/code.rs")).unwrap();
+        assert_eq!(nested.config.license_header, "Apache License\nCopyright 2020 Vendor");
+        assert!(nested.is_ignored(&vendor.join("lib/api.gen.rs")));
+        assert!(!nested.is_ignored(&vendor.join("lib/code.rs")));
+
+        let sibling = hierarchy.layer_for(&temp_dir.path().join("main.rs")).unwrap();
+        assert_eq!(sibling.config.license_header, "MIT License\nCopyright 2024");
+    }
+
+    #[test]
+    fn loaded_config_replaces_root_nested_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let sub = temp_dir.path().join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(temp_dir.path().join(NESTED_CONFIG_FILE), "license_header = \"Root file\"\n")
+            .unwrap();
+        fs::write(sub.join(NESTED_CONFIG_FILE), "ignore_patterns = [\"*.gen.rs\"]\n").unwrap();
+
+        // As with `--config other.toml`: the root's file is not layered on top
+        let hierarchy = ConfigHierarchy::new(temp_dir.path(), base_config()).unwrap();
+        let root = hierarchy.layer_for(&temp_dir.path().join("main.rs")).unwrap();
+        assert_eq!(root.config.license_header, "MIT License\nCopyright 2024");
+
+        // Nested files below the root still apply
+        let nested = hierarchy.layer_for(&sub.join("lib.rs")).unwrap();
+        assert_eq!(nested.config.license_header, "MIT License\nCopyright 2024");
+        assert!(nested.is_ignored(&sub.join("api.gen.rs")));
+    }
+
+    #[test]
+    fn third_party_rules_match_relative_to_their_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendor = temp_dir.path().join("vendor");
+        fs::create_dir_all(vendor.join("zlib")).unwrap();
+        fs::write(
+            vendor.join(NESTED_CONFIG_FILE),
+            "[[third_party]]\npaths = [\"zlib/\"]\nallowed_licenses = [\"Zlib\"]\n",
+        )
+        .unwrap();
+
+        let config = base_config().with_third_party(ThirdPartyRule::new(["/extern/"]));
+        let hierarchy = ConfigHierarchy::new(temp_dir.path(), config).unwrap();
+
+        let root = hierarchy.layer_for(&temp_dir.path().join("extern/a.c")).unwrap();
+        assert!(root.third_party_rule(&temp_dir.path().join("extern/a.c")).is_some());
+        assert!(root.third_party_rule(&temp_dir.path().join("src/extern/a.c")).is_none());
+
+        let nested = hierarchy.layer_for(&vendor.join("zlib/inflate.c")).unwrap();
+        let rule = nested.third_party_rule(&vendor.join("zlib/inflate.c")).unwrap();
+        assert_eq!(rule.allowed_licenses, vec!["Zlib".to_string()]);
+        // Inherited rules are kept, still rooted at the scan root
+        assert_eq!(nested.config.third_party.len(), 2);
+        assert!(nested.third_party_rule(&vendor.join("extern/a.c")).is_none());
+    }
+
+    #[test]
+    fn invalid_nested_config_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join("sub").join(NESTED_CONFIG_FILE), "not = [valid").unwrap();
+
+        let hierarchy = ConfigHierarchy::new(temp_dir.path(), base_config()).unwrap();
+        assert!(hierarchy.layer_for(&temp_dir.path().join("sub/a.rs")).is_err());
+    }
+}
@@ -4,27 +4,35 @@
 //! content filtering, and license header checking.
 
 pub mod filter;
+pub mod hierarchy;
+pub mod packages;
 pub mod walker;
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::time::Instant;
 
 use rayon::iter::ParallelIterator;
 
-use crate::checker::HeaderChecker;
 use crate::config::Config;
 use crate::error::{Result, ScannerError};
-use crate::types::{FilePath, ScanResult, ScanSummary};
+use crate::types::{
+    CopyrightResult, FileEvidence, FilePath, FileStatus, PolicyResult, PolicyVerdict, ScanResult,
+    ScanSummary, SkipReason,
+};
 
-use self::filter::should_process_file;
-use self::walker::{FileWalker, WalkEntry};
+use self::filter::should_process_path;
+use self::hierarchy::ConfigHierarchy;
+use self::packages::check_packages;
+use self::walker::{access_error_reason, FileWalker, WalkEntry};
 
 /// Main scanner that coordinates walking and checking
 #[derive(Debug)]
 pub struct Scanner {
     walker: FileWalker,
-    checker: HeaderChecker,
+    hierarchy: ConfigHierarchy,
     config: Config,
+    evidence: bool,
 }
 
 impl Scanner {
@@ -53,14 +61,22 @@ impl Scanner {
             }));
         }
 
-        let walker = FileWalker::new(root_path)
-            .with_ignores(config.ignore_patterns.clone())
+        let walker = FileWalker::for_config(root_path, &config)?
             .with_parallelism(config.parallel_jobs.unwrap_or(num_cpus::get()));
 
-        // Create header checker for actual header detection
-        let checker = HeaderChecker::new(&config)?;
+        // Header checkers for the root and any nested .flc.toml directories
+        let hierarchy = ConfigHierarchy::new(root_path, config.clone())?;
 
-        Ok(Self { walker, checker, config })
+        Ok(Self { walker, hierarchy, config, evidence: false })
+    }
+
+    /// Hash every file and record its license evidence in the summary.
+    ///
+    /// Files are then read in full, in the same pass as the header check, instead of
+    /// only up to `max_header_bytes`.
+    pub fn with_evidence(mut self, evidence: bool) -> Self {
+        self.evidence = evidence;
+        self
     }
 
     /// Scan all files and return results
@@ -69,29 +85,83 @@ impl Scanner {
         let start = Instant::now();
 
         // Walk files and process them in parallel
-        let results: Vec<ScanResult> = self
+        let checked: Vec<CheckedFile> = self
             .walker
             .walk()
             .filter_map(|entry_result| {
                 match entry_result {
-                    Ok(entry) => Some(self.check_file(&entry)),
-                    Err(e) => {
-                        // Log the error but continue processing
-                        tracing::warn!("Error walking directory entry: {}", e);
-                        None
-                    }
+                    Ok(entry) => self.check_file(&entry).transpose(),
+                    Err(e) => match access_error_reason(&e) {
+                        // Report unreadable paths as results so they reach the output
+                        Some(reason) => Some(Ok(self.unreadable_result(reason).into())),
+                        None => {
+                            tracing::warn!("Error walking directory entry: {}", e);
+                            None
+                        }
+                    },
                 }
             })
-            .collect();
+            .collect::<Result<Vec<_>>>()?;
+        let mut results = Vec::with_capacity(checked.len());
+        let mut evidence = Vec::new();
+        let mut policy = Vec::new();
+        let mut copyright = Vec::new();
+        let mut licenses = Vec::new();
+        for file in checked {
+            // Third-party files keep their own licenses and are not compared with packages
+            if !file.result.status.is_third_party() {
+                licenses.push((file.result.path.clone(), file.license));
+            }
+            results.push(file.result);
+            evidence.extend(file.evidence);
+            policy.extend(file.policy);
+            copyright.extend(file.copyright);
+        }
 
         let duration = start.elapsed();
+        let unreadable: Vec<ScanResult> =
+            results.iter().filter(|r| r.status.access_error().is_some()).cloned().collect();
+        for result in &unreadable {
+            tracing::warn!("Could not read {}: {}", result.path, result.status);
+        }
+
+        // A denied license or a disallowed copyright holder fails a file that otherwise passes
+        let rejected: HashSet<&FilePath> = policy
+            .iter()
+            .filter(|decision| decision.verdict == PolicyVerdict::Denied)
+            .map(|decision| &decision.path)
+            .chain(copyright.iter().filter(|c| c.issue.is_failure()).map(|c| &c.path))
+            .collect();
+        let passed = |r: &&ScanResult| r.status.is_passed() && !rejected.contains(&r.path);
+        let packages = if self.config.check_packages {
+            check_packages(licenses.iter().map(|(path, license)| (path.as_path(), license.clone())))
+        } else {
+            Vec::new()
+        };
+
         let summary = ScanSummary::new(
             results.len(),
-            results.iter().filter(|r| r.status.has_valid_header()).count(),
-            results.iter().filter(|r| r.status.is_missing_header()).count(),
+            results.iter().filter(passed).count(),
+            results.iter().filter(|r| r.status.is_failure() || rejected.contains(&r.path)).count(),
             results.iter().filter(|r| r.status.is_skipped()).count(),
             duration,
-        );
+        )
+        .with_too_large(
+            results
+                .iter()
+                .filter(|r| matches!(r.status.skip_reason(), Some(SkipReason::TooLarge { .. })))
+                .count(),
+        )
+        .with_unreadable(unreadable)
+        .with_suppressed(results.iter().filter(|r| r.status.is_suppressed()).cloned().collect())
+        .with_third_party(results.iter().filter(|r| r.status.is_third_party()).cloned().collect())
+        .with_foreign_licenses(
+            results.iter().filter(|r| r.status.is_foreign_license()).cloned().collect(),
+        )
+        .with_policy(policy)
+        .with_copyright(copyright)
+        .with_packages(packages)
+        .with_evidence(evidence);
 
         tracing::info!("Scan completed: {} files in {:.2}s", summary.total, duration.as_secs_f64());
 
@@ -99,78 +169,158 @@ impl Scanner {
     }
 
     /// Check a single file and return the result
+    ///
+    /// Returns `None` for files excluded by a nested config's ignore patterns.
     #[tracing::instrument(skip(self, entry))]
-    fn check_file(&self, entry: &WalkEntry) -> ScanResult {
+    fn check_file(&self, entry: &WalkEntry) -> Result<Option<CheckedFile>> {
+        // Configuration from the nearest nested .flc.toml, or the root
+        let layer = self.hierarchy.layer_for(&entry.path)?;
+        if layer.is_ignored(&entry.path) {
+            return Ok(None);
+        }
+        let (checker, config) = (&layer.checker, &layer.config);
+
         let file_path = match FilePath::new_existing(entry.path.clone()) {
             Ok(fp) => fp,
             Err(_) => {
-                // File no longer exists or is not accessible
-                return ScanResult::new(
-                    FilePath::new(entry.path.clone()),
-                    crate::types::FileStatus::Skipped {
-                        reason: crate::types::SkipReason::Gitignored, // Generic skip reason
-                    },
-                );
+                // File vanished or stopped being a regular file since it was walked
+                let error = std::fs::metadata(&entry.path).err().unwrap_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a regular file")
+                });
+                let reason = SkipReason::from_io_error(&entry.path, &error);
+                return Ok(Some(self.unreadable_result(reason).into()));
             }
         };
 
-        // Read file content
-        let content = match self.read_file_content(&entry.path) {
-            Ok(content) => content,
-            Err(_) => {
-                // Could not read file
-                return ScanResult::new(
-                    file_path,
-                    crate::types::FileStatus::Skipped {
-                        reason: crate::types::SkipReason::Gitignored, // Generic skip reason
-                    },
-                );
+        // Skip oversized files from walker metadata, before reading any content
+        if let Some(reason) = entry.exceeds_size(config.max_file_size) {
+            return Ok(Some(ScanResult::new(file_path, FileStatus::Skipped { reason }).into()));
+        }
+
+        // Read file content, hashing the whole file when collecting evidence
+        let read = if self.evidence {
+            read_file_hashed(&entry.path, config.max_header_bytes)
+                .map(|(content, sha256)| (content, Some(sha256)))
+        } else {
+            read_file_content(&entry.path, config.max_header_bytes).map(|content| (content, None))
+        };
+        let (content, sha256) = match read {
+            Ok(read) => read,
+            Err(error) => {
+                let reason = SkipReason::from_io_error(&entry.path, &error);
+                return Ok(Some(self.unreadable_result(reason).into()));
             }
         };
 
         // Check if file should be processed
-        let extension = entry.extension();
-        match should_process_file(&content, extension, &self.config) {
-            Ok(_) => {
-                // File should be processed - check license header using HeaderChecker
-                let status = self.checker.check_content(&content, extension);
-                ScanResult::new(file_path, status)
-            }
-            Err(reason) => {
-                // File should be skipped
-                ScanResult::new(file_path, crate::types::FileStatus::Skipped { reason })
-            }
-        }
+        let status = match should_process_path(&content, &entry.path, checker.styles(), config) {
+            // Vendored code is classified by its own license
+            Ok(_) => match layer.third_party_rule(&entry.path) {
+                Some(rule) => checker.check_third_party(&content, &entry.path, rule),
+                None => checker.check_content(&content, &entry.path),
+            },
+            // File should be skipped
+            Err(reason) => FileStatus::Skipped { reason },
+        };
+        // The license is only needed for evidence, the policy and package checks
+        let wants_license =
+            sha256.is_some() || !config.policy.is_empty() || self.config.check_packages;
+        let license = if wants_license { layer.file_license(&status, &content) } else { None };
+        let evidence = sha256.map(|sha256| FileEvidence {
+            path: file_path.clone(),
+            license: license.clone(),
+            sha256,
+        });
+        let result = ScanResult::new(file_path, status);
+        let policy = layer.policy_result(&result, license.as_deref());
+        let history = self.hierarchy.file_history(&layer, &entry.path);
+        let copyright = layer.copyright_results(&result, &content, history);
+        Ok(Some(CheckedFile { result, license, evidence, policy, copyright }))
     }
 
-    /// Read file content up to the configured maximum bytes
-    #[tracing::instrument]
-    fn read_file_content(&self, path: &Path) -> std::io::Result<Vec<u8>> {
-        use std::fs::File;
-        use std::io::Read;
+    /// Build the result for a path that could not be read
+    fn unreadable_result(&self, reason: SkipReason) -> ScanResult {
+        let path = reason.access_error().map(|(path, _)| path.to_path_buf()).unwrap_or_default();
+        ScanResult::new(
+            FilePath::new(path),
+            FileStatus::unreadable(reason, self.config.fail_on_unreadable),
+        )
+    }
+}
 
-        let file = File::open(path)?;
-        let mut buffer = Vec::new();
+/// A checked file with what else was gathered from its content
+struct CheckedFile {
+    result: ScanResult,
+    /// The recognised license, when evidence, a policy or package checks need it
+    license: Option<String>,
+    /// Checksum and license, when evidence is collected
+    evidence: Option<FileEvidence>,
+    /// Policy decision, when a policy applies
+    policy: Option<PolicyResult>,
+    /// Flagged copyright holders
+    copyright: Vec<CopyrightResult>,
+}
 
-        // Read up to max_header_bytes + some buffer for safety
-        // Use checked_add to prevent overflow (though unlikely in practice)
-        let max_read =
-            self.config.max_header_bytes.checked_add(1024).unwrap_or(self.config.max_header_bytes);
-        file.take(max_read as u64).read_to_end(&mut buffer)?;
+impl From<ScanResult> for CheckedFile {
+    fn from(result: ScanResult) -> Self {
+        Self { result, license: None, evidence: None, policy: None, copyright: Vec::new() }
+    }
+}
 
-        // Truncate to the configured maximum
-        if buffer.len() > self.config.max_header_bytes {
-            buffer.truncate(self.config.max_header_bytes);
-        }
+/// Read file content up to the configured maximum bytes
+#[tracing::instrument]
+fn read_file_content(path: &Path, max_header_bytes: usize) -> std::io::Result<Vec<u8>> {
+    use std::fs::File;
+    use std::io::Read;
+
+    let file = File::open(path)?;
+    let mut buffer = Vec::new();
+
+    // Read up to max_header_bytes + some buffer for safety
+    // Use checked_add to prevent overflow (though unlikely in practice)
+    let max_read = max_header_bytes.checked_add(1024).unwrap_or(max_header_bytes);
+    file.take(max_read as u64).read_to_end(&mut buffer)?;
 
-        Ok(buffer)
+    // Truncate to the configured maximum
+    if buffer.len() > max_header_bytes {
+        buffer.truncate(max_header_bytes);
     }
+
+    Ok(buffer)
+}
+
+/// Read the whole file, returning its first `max_header_bytes` and its SHA-256
+#[tracing::instrument]
+fn read_file_hashed(path: &Path, max_header_bytes: usize) -> std::io::Result<(Vec<u8>, String)> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut header = Vec::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let chunk = buffer.get(..read).unwrap_or_default();
+        hasher.update(chunk);
+        let wanted = max_header_bytes.saturating_sub(header.len()).min(chunk.len());
+        header.extend_from_slice(chunk.get(..wanted).unwrap_or_default());
+    }
+
+    Ok((header, crate::inventory::hex(&hasher.finalize())))
 }
 
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use crate::config::ThirdPartyRule;
+    use crate::types::{CopyrightIssue, PackageIssue};
     use std::fs;
     use tempfile::TempDir;
 
@@ -246,10 +396,7 @@ mod tests {
         config.license_header = "MIT License\n\nCopyright 2024".to_string();
         // Add comment style for .rs files
         use crate::config::CommentStyleConfig;
-        config.comment_styles.insert(
-            "rs".to_string(),
-            CommentStyleConfig { prefix: "//".to_string(), suffix: None },
-        );
+        config.comment_styles.insert("rs".to_string(), CommentStyleConfig::line_comment("//"));
 
         let temp_dir = TempDir::new().unwrap();
 
@@ -301,6 +448,336 @@ mod tests {
         assert_eq!(summary.skipped, 1);
     }
 
+    #[test]
+    fn scanner_checks_files_by_name() {
+        let mut config = Config::default();
+        config.license_header = "MIT License\nCopyright 2024".to_string();
+        let temp_dir = TempDir::new().unwrap();
+
+        // Extensionless file resolved through the file name table
+        fs::write(temp_dir.path().join("Dockerfile"), "FROM alpine\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.skipped, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scanner_reports_broken_symlinks() {
+        let mut config = Config::default();
+        config.license_header = "MIT License\nCopyright 2024".to_string();
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("dangling.rs");
+        std::os::unix::fs::symlink(temp_dir.path().join("missing.rs"), &link).unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config.clone()).unwrap();
+        let summary = scanner.scan().unwrap();
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.unreadable.len(), 1);
+        let result = summary.unreadable.first().unwrap();
+        assert_eq!(result.path.as_path(), link.as_path());
+        assert!(matches!(result.status.access_error(), Some(SkipReason::BrokenSymlink { .. })));
+
+        let strict = config.with_fail_on_unreadable(true);
+        let summary = Scanner::new(&temp_dir, strict).unwrap().scan().unwrap();
+        assert_eq!(summary.skipped, 0);
+        assert_eq!(summary.failed, 1);
+        assert!(summary.unreadable.first().unwrap().status.is_unreadable());
+    }
+
+    #[test]
+    fn scanner_skip_files_over_max_size() {
+        let config = Config::default()
+            .with_license_header("MIT License\nCopyright 2024")
+            .with_max_file_size(64);
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(temp_dir.path().join("bundle.min.js"), "x".repeat(1000)).unwrap();
+        fs::write(temp_dir.path().join("small.js"), "let x = 1;\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.too_large, 1);
+        assert_eq!(summary.failed, 1);
+    }
+
+    #[test]
+    fn scanner_skips_suppressed_files() {
+        let config = Config::default().with_license_header("MIT License\nCopyright 2024");
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(
+            temp_dir.path().join("gen.rs"),
+            "// flc:ignore-file: generated by build.rs\nfn gen() {}\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("lib.rs"), "fn lib() {}\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.suppressed.len(), 1);
+        assert_eq!(
+            summary.suppressed.first().unwrap().status.to_string(),
+            "skipped (suppressed by flc:ignore-file: generated by build.rs)"
+        );
+    }
+
+    #[test]
+    fn scanner_classifies_third_party_files() {
+        let config =
+            Config::default().with_license_header("MIT License\nCopyright 2024").with_third_party(
+                ThirdPartyRule::new(["vendor/"]).with_allowed_license("BSD-3-Clause"),
+            );
+        let temp_dir = TempDir::new().unwrap();
+        let vendor = temp_dir.path().join("vendor");
+        fs::create_dir_all(&vendor).unwrap();
+
+        fs::write(vendor.join("ours.rs"), "// MIT License\n// Copyright 2024\n\nfn a() {}\n")
+            .unwrap();
+        fs::write(vendor.join("bsd.rs"), "// SPDX-License-Identifier: BSD-3-Clause\nfn b() {}\n")
+            .unwrap();
+        fs::write(vendor.join("gpl.rs"), "// SPDX-License-Identifier: GPL-2.0-only\nfn c() {}\n")
+            .unwrap();
+        fs::write(vendor.join("bare.rs"), "fn d() {}\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.failed, 2);
+        assert_eq!(summary.third_party.len(), 4);
+
+        let status = |name: &str| {
+            let result = summary.third_party.iter().find(|r| r.path.as_path().ends_with(name));
+            result.unwrap().status.to_string()
+        };
+        assert_eq!(status("ours.rs"), "third-party (our header)");
+        assert_eq!(status("bsd.rs"), "third-party (known license BSD-3-Clause)");
+        assert_eq!(status("gpl.rs"), "third-party (known license GPL-2.0-only, not allowed)");
+        assert_eq!(status("bare.rs"), "third-party (no license, not allowed)");
+    }
+
+    #[test]
+    fn scanner_applies_license_policy() {
+        let mut config = Config::default().with_license_header("MIT License\nCopyright 2024");
+        config.policy = toml::from_str(
+            r#"
+            deny = ["GPL-3.0-only"]
+
+            [[paths]]
+            paths = ["services/"]
+            deny = ["AGPL-3.0-only"]
+            "#,
+        )
+        .unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let services = temp_dir.path().join("services");
+        fs::create_dir_all(&services).unwrap();
+
+        let agpl = "// SPDX-License-Identifier: AGPL-3.0-only\nfn a() {}\n";
+        fs::write(temp_dir.path().join("tool.rs"), agpl).unwrap();
+        fs::write(services.join("api.rs"), agpl).unwrap();
+        fs::write(services.join("main.rs"), "// MIT License\n// Copyright 2024\n\nfn b() {}\n")
+            .unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.policy.len(), 3);
+        let verdict = |name: &str| {
+            let decision = summary.policy.iter().find(|d| d.path.as_path().ends_with(name));
+            decision.unwrap().verdict
+        };
+        assert_eq!(verdict("tool.rs"), PolicyVerdict::Allowed);
+        assert_eq!(verdict("services/api.rs"), PolicyVerdict::Denied);
+        assert_eq!(verdict("services/main.rs"), PolicyVerdict::Allowed);
+    }
+
+    #[test]
+    fn scanner_applies_policy_to_header_license() {
+        let mut config = Config::default().with_license_header("SPDX-License-Identifier: MIT");
+        config.policy = toml::from_str(
+            r#"
+            [[paths]]
+            paths = ["services/"]
+            deny = ["AGPL-3.0-or-later"]
+            "#,
+        )
+        .unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let api = temp_dir.path().join("services/api");
+        fs::create_dir_all(&api).unwrap();
+        fs::write(api.join("s.rs"), "// SPDX-License-Identifier: AGPL-3.0-or-later\nfn s() {}\n")
+            .unwrap();
+        fs::write(api.join("t.rs"), "// SPDX-License-Identifier: MIT\nfn t() {}\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        let decision =
+            |name: &str| summary.policy.iter().find(|d| d.path.as_path().ends_with(name)).unwrap();
+        assert_eq!(decision("s.rs").license, "AGPL-3.0-or-later");
+        assert_eq!(decision("s.rs").verdict, PolicyVerdict::Denied);
+        assert_eq!(decision("t.rs").license, "MIT");
+        assert_eq!(decision("t.rs").verdict, PolicyVerdict::Allowed);
+    }
+
+    #[test]
+    fn scanner_flags_copyright_holders() {
+        let mut config = Config::default().with_license_header("MIT License");
+        config.copyright = toml::from_str(
+            r#"
+            holders = ["Acme Corp"]
+            aliases = { "ACME Corporation" = "Acme Corp" }
+            "#,
+        )
+        .unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let file = |name: &str, holder: &str| {
+            let content = format!("// MIT License\n// Copyright 2024 {}\n\nfn f() {{}}\n", holder);
+            fs::write(temp_dir.path().join(name), content).unwrap();
+        };
+        file("ours.rs", "Acme Corp");
+        file("variant.rs", "ACME Corporation");
+        file("person.rs", "John Smith");
+        file("other.rs", "Initech LLC");
+
+        let summary = Scanner::new(&temp_dir, config).unwrap().scan().unwrap();
+
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.failed, 2);
+        let issue = |name: &str| {
+            let result = summary.copyright.iter().find(|c| c.path.as_path().ends_with(name));
+            result.map(|c| c.issue.clone())
+        };
+        assert_eq!(issue("ours.rs"), None);
+        assert_eq!(
+            issue("variant.rs"),
+            Some(CopyrightIssue::NonCanonical { canonical: "Acme Corp".to_string() })
+        );
+        assert_eq!(issue("person.rs"), Some(CopyrightIssue::IndividualHolder));
+        assert_eq!(issue("other.rs"), Some(CopyrightIssue::UnknownHolder));
+    }
+
+    #[test]
+    fn scanner_checks_package_license_files() {
+        let config = Config::default().with_license_header("MIT License\nCopyright 2024");
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["a", "b"] {
+            let package = temp_dir.path().join("crates").join(name);
+            fs::create_dir_all(package.join("src")).unwrap();
+            fs::write(package.join("Cargo.toml"), "[package]\n").unwrap();
+            fs::write(package.join("src/lib.rs"), "// MIT License\n// Copyright 2024\n\n").unwrap();
+        }
+        fs::write(temp_dir.path().join("crates/a/LICENSE"), "SPDX-License-Identifier: MIT\n")
+            .unwrap();
+
+        let summary = Scanner::new(&temp_dir, config.clone()).unwrap().scan().unwrap();
+        assert!(summary.packages.is_empty());
+
+        let config = config.with_check_packages(true);
+        let summary = Scanner::new(&temp_dir, config).unwrap().scan().unwrap();
+        assert_eq!(summary.packages.len(), 1);
+        let package = summary.packages.first().unwrap();
+        assert!(package.manifest.as_path().ends_with("crates/b/Cargo.toml"));
+        assert_eq!(package.issue, PackageIssue::MissingLicenseFile);
+        assert_eq!(crate::error::ExitStatus::from_summary(&summary).code(), 1);
+    }
+
+    #[test]
+    fn scanner_collects_evidence() {
+        let config = Config::default()
+            .with_license_header("MIT License\nCopyright 2024")
+            .with_max_header_bytes(256);
+        let temp_dir = TempDir::new().unwrap();
+        let content =
+            format!("// MIT License\n// Copyright 2024\n\n{}", "fn main() {}\n".repeat(50));
+        fs::write(temp_dir.path().join("main.rs"), &content).unwrap();
+        fs::write(temp_dir.path().join("lib.rs"), "fn lib() {}\n").unwrap();
+
+        let summary = Scanner::new(&temp_dir, config.clone()).unwrap().scan().unwrap();
+        assert!(summary.evidence.is_empty());
+
+        let summary = Scanner::new(&temp_dir, config).unwrap().with_evidence(true).scan().unwrap();
+        assert_eq!(summary.evidence.len(), 2);
+        let main = summary.evidence.iter().find(|e| e.path.as_path().ends_with("main.rs"));
+        let main = main.unwrap();
+        assert_eq!(main.license.as_deref(), Some("MIT"));
+        // The hash covers the whole file, not just the header bytes
+        assert_eq!(
+            main.sha256,
+            crate::inventory::hex(&<sha2::Sha256 as sha2::Digest>::digest(content))
+        );
+        let lib = summary.evidence.iter().find(|e| e.path.as_path().ends_with("lib.rs"));
+        assert_eq!(lib.unwrap().license, None);
+    }
+
+    #[test]
+    fn scanner_applies_nested_config() {
+        let config = Config::default().with_license_header("MIT License\nCopyright 2024");
+        let temp_dir = TempDir::new().unwrap();
+        let vendor = temp_dir.path().join("vendor");
+        fs::create_dir_all(&vendor).unwrap();
+
+        fs::write(temp_dir.path().join("main.rs"), "// MIT License\n// Copyright 2024\n\n")
+            .unwrap();
+        fs::write(
+            vendor.join(".flc.toml"),
+            "license_header = \"Apache License\"\nignore_patterns = [\"*.gen.rs\"]\n",
+        )
+        .unwrap();
+        fs::write(vendor.join("lib.rs"), "// Apache License\n\nfn lib() {}\n").unwrap();
+        fs::write(vendor.join("api.gen.rs"), "fn generated() {}\n").unwrap();
+
+        let scanner = Scanner::new(&temp_dir, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.failed, 0);
+    }
+
+    #[test]
+    fn scanner_applies_nested_config_under_relative_root() {
+        let mut config = Config::default().with_license_header("MIT License\nCopyright 2024");
+        config.policy = toml::from_str("deny = [\"GPL-2.0-only\"]").unwrap();
+        // A path like `./.tmpXXXX`, as when scanning `.`
+        let temp_dir = TempDir::new_in(".").unwrap();
+        let root = Path::new(".").join(temp_dir.path().file_name().unwrap());
+        let sub = root.join("sub");
+        fs::create_dir_all(sub.join("vendor")).unwrap();
+
+        fs::write(
+            sub.join(".flc.toml"),
+            "ignore_patterns = [\"gen.rs\"]\n\
+             [[third_party]]\npaths = [\"vendor/\"]\nallowed_licenses = [\"GPL-2.0-only\"]\n",
+        )
+        .unwrap();
+        fs::write(sub.join("gen.rs"), "fn generated() {}\n").unwrap();
+        fs::write(sub.join("vendor/v.c"), "// SPDX-License-Identifier: GPL-2.0-only\nint v;\n")
+            .unwrap();
+
+        let scanner = Scanner::new(&root, config).unwrap();
+        let summary = scanner.scan().unwrap();
+
+        assert_eq!(summary.total, 1);
+        assert_eq!(summary.third_party.len(), 1);
+        assert_eq!(summary.passed, 0);
+    }
+
     #[test]
     fn scanner_skip_unknown_extensions() {
         let mut config = Config::default();
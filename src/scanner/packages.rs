@@ -0,0 +1,321 @@
+//! Package license files.
+//!
+//! A package is a directory with a manifest such as `Cargo.toml` or
+//! `package.json`, other than a workspace root. Each package needs a LICENSE
+//! or COPYING file that is recognised as a known license and covers every
+//! license found in the headers of the package's own files (files of nested
+//! packages belong to those).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::checker::licenses::{identify_license, license_ids};
+use crate::types::{FilePath, LicenseClassification, PackageIssue, PackageResult};
+
+/// Files that make their directory a package
+pub const PACKAGE_MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "composer.json",
+    "Gemfile",
+];
+
+/// File name prefixes of license files, e.g. `LICENSE`, `LICENSE-MIT`, `COPYING.LESSER`
+const LICENSE_FILE_PREFIXES: [&str; 3] = ["LICENSE", "LICENCE", "COPYING"];
+
+/// Whether a file name is that of a license file (case-insensitive)
+pub fn is_license_file(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    LICENSE_FILE_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Check the license files of the packages among `files`
+///
+/// `files` are the scanned files with the license recognised in each header;
+/// manifests among them mark package directories, so ignored directories such as
+/// `node_modules/` are not checked. Returns the packages with a problem, in path
+/// order.
+#[tracing::instrument(skip(files))]
+pub fn check_packages<'a>(
+    files: impl IntoIterator<Item = (&'a Path, Option<String>)>,
+) -> Vec<PackageResult> {
+    let files: Vec<(&Path, Option<String>)> = files.into_iter().collect();
+
+    let mut manifests: BTreeMap<PathBuf, &Path> = BTreeMap::new();
+    for (path, _) in &files {
+        let is_manifest = path
+            .file_name()
+            .is_some_and(|name| PACKAGE_MANIFESTS.iter().any(|manifest| name == *manifest));
+        if let (true, Some(dir)) = (is_manifest && is_package(path), path.parent()) {
+            // One manifest per package is enough to report against
+            manifests.entry(dir.to_path_buf()).or_insert(path);
+        }
+    }
+
+    // License identifiers used in headers, per nearest package
+    let mut header_ids: BTreeMap<&Path, BTreeSet<String>> = BTreeMap::new();
+    for (path, license) in &files {
+        let Some(license) = license else { continue };
+        let package = path.ancestors().skip(1).find(|dir| manifests.contains_key(*dir));
+        if let Some(package) = package {
+            header_ids.entry(package).or_default().extend(license_ids(license));
+        }
+    }
+
+    manifests
+        .iter()
+        .filter_map(|(dir, manifest)| {
+            let license_files = match license_files(dir) {
+                Ok(license_files) => license_files,
+                Err(e) => {
+                    tracing::warn!("Could not list {}: {}", dir.display(), e);
+                    return None;
+                }
+            };
+            let licenses = license_file_ids(&license_files);
+            let issue = if license_files.is_empty() {
+                PackageIssue::MissingLicenseFile
+            } else if licenses.is_empty() {
+                PackageIssue::UnrecognisedLicenseFile
+            } else {
+                let uncovered: Vec<String> = header_ids
+                    .get(dir.as_path())
+                    .into_iter()
+                    .flatten()
+                    .filter(|id| !is_covered(&licenses, id))
+                    .cloned()
+                    .collect();
+                if uncovered.is_empty() {
+                    return None;
+                }
+                PackageIssue::HeaderMismatch { uncovered }
+            };
+            Some(PackageResult {
+                manifest: FilePath::new(manifest.to_path_buf()),
+                licenses: licenses.into_iter().collect(),
+                issue,
+            })
+        })
+        .collect()
+}
+
+/// Whether the license file ids cover a header license id
+///
+/// A license text does not say whether later versions may be used, so the
+/// `-only` and `-or-later` ids of the same license cover each other.
+fn is_covered(licenses: &BTreeSet<String>, id: &str) -> bool {
+    let base = |id: &str| {
+        id.strip_suffix("-only").or_else(|| id.strip_suffix("-or-later")).unwrap_or(id).to_string()
+    };
+    licenses.contains(id) || licenses.iter().any(|license| base(license) == base(id))
+}
+
+/// Whether a manifest describes a package of its own
+///
+/// A `Cargo.toml` without `[package]` is a virtual workspace, and a `package.json`
+/// declaring `"workspaces"` or `"private": true` is a monorepo root or is never
+/// published; neither needs a license file. Unreadable manifests count as packages.
+fn is_package(manifest: &Path) -> bool {
+    let name = manifest.file_name().and_then(|name| name.to_str());
+    if !matches!(name, Some("Cargo.toml" | "package.json")) {
+        return true;
+    }
+    let Ok(content) = std::fs::read_to_string(manifest) else { return true };
+    match name {
+        Some("Cargo.toml") => toml::from_str::<toml::Table>(&content)
+            .map_or(true, |manifest| manifest.contains_key("package")),
+        _ => serde_json::from_str::<serde_json::Value>(&content).map_or(true, |manifest| {
+            manifest.get("workspaces").is_none()
+                && manifest.get("private").and_then(serde_json::Value::as_bool) != Some(true)
+        }),
+    }
+}
+
+/// The license files directly inside `dir`, sorted by name
+fn license_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut license_files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_license =
+            path.file_name().is_some_and(|name| is_license_file(&name.to_string_lossy()));
+        if is_license && path.is_file() {
+            license_files.push(path);
+        }
+    }
+    license_files.sort();
+    Ok(license_files)
+}
+
+/// License ids recognised in license files; unreadable files are logged and left out
+fn license_file_ids(license_files: &[PathBuf]) -> BTreeSet<String> {
+    let mut ids = BTreeSet::new();
+    for path in license_files {
+        match std::fs::read(path) {
+            Ok(content) => {
+                if let LicenseClassification::Known { id } = identify_license(&content) {
+                    ids.extend(license_ids(&id));
+                }
+            }
+            Err(e) => tracing::warn!("Could not read {}: {}", path.display(), e),
+        }
+    }
+    ids
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MIT: &str = "MIT License\n\nCopyright (c) 2024 Acme Corp\n\n\
+        Permission is hereby granted, free of charge, to any person obtaining a copy\n\
+        of this software and associated documentation files (the \"Software\"), to deal\n\
+        in the Software without restriction, including without limitation the rights\n\
+        to use, copy, modify, merge, publish, distribute, sublicense, and/or sell\n";
+
+    const GPL_3: &str = "                    GNU GENERAL PUBLIC LICENSE\n\
+        \x20                      Version 3, 29 June 2007\n\n\
+        \x20Copyright (C) 2007 Free Software Foundation, Inc. <https://fsf.org/>\n\
+        \x20Everyone is permitted to copy and distribute verbatim copies\n\
+        \x20of this license document, but changing it is not allowed.\n\n\
+        \x20                           Preamble\n\n\
+        \x20 The GNU General Public License is a free, copyleft license for\n\
+        software and other kinds of works.\n";
+
+    #[test]
+    fn recognises_license_file_names() {
+        assert!(is_license_file("LICENSE"));
+        assert!(is_license_file("license.md"));
+        assert!(is_license_file("LICENSE-APACHE"));
+        assert!(is_license_file("COPYING.LESSER"));
+        assert!(is_license_file("Licence.txt"));
+        assert!(!is_license_file("README.md"));
+    }
+
+    #[test]
+    fn checks_license_files_per_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["ok", "missing", "mismatch", "unknown", "dual/src"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("ok/LICENSE"), MIT).unwrap();
+        fs::write(root.join("mismatch/LICENSE.md"), MIT).unwrap();
+        fs::write(root.join("unknown/COPYING"), "All rights reserved by nobody.\n").unwrap();
+        fs::write(root.join("dual/LICENSE-MIT"), MIT).unwrap();
+        fs::write(root.join("dual/LICENSE-APACHE"), "SPDX-License-Identifier: Apache-2.0\n")
+            .unwrap();
+
+        let paths: Vec<PathBuf> = [
+            "README.md",
+            "ok/Cargo.toml",
+            "ok/lib.rs",
+            "missing/package.json",
+            "mismatch/pyproject.toml",
+            "mismatch/a.py",
+            "mismatch/b.py",
+            "unknown/go.mod",
+            "dual/Cargo.toml",
+            "dual/src/lib.rs",
+        ]
+        .iter()
+        .map(|path| root.join(path))
+        .collect();
+        let license = |path: &Path| -> Option<String> {
+            let name = path.file_name()?.to_str()?;
+            match name {
+                "lib.rs" if path.starts_with(root.join("dual")) => {
+                    Some("MIT OR Apache-2.0".to_string())
+                }
+                "lib.rs" | "a.py" | "README.md" => Some("MIT".to_string()),
+                "b.py" => Some("GPL-3.0-only WITH GCC-exception-3.1".to_string()),
+                _ => None,
+            }
+        };
+
+        let results = check_packages(paths.iter().map(|path| (path.as_path(), license(path))));
+        let issues: Vec<(PathBuf, &PackageIssue)> = results
+            .iter()
+            .map(|result| {
+                (result.manifest.as_path().strip_prefix(root).unwrap().to_path_buf(), &result.issue)
+            })
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    PathBuf::from("mismatch/pyproject.toml"),
+                    &PackageIssue::HeaderMismatch { uncovered: vec!["GPL-3.0-only".to_string()] }
+                ),
+                (PathBuf::from("missing/package.json"), &PackageIssue::MissingLicenseFile),
+                (PathBuf::from("unknown/go.mod"), &PackageIssue::UnrecognisedLicenseFile),
+            ]
+        );
+        assert_eq!(results.first().unwrap().licenses, vec!["MIT".to_string()]);
+    }
+
+    #[test]
+    fn gpl_license_file_covers_only_and_or_later_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"gpl\"\n").unwrap();
+        fs::write(root.join("COPYING"), GPL_3).unwrap();
+        let licenses = license_file_ids(&license_files(root).unwrap());
+        assert_eq!(licenses.into_iter().collect::<Vec<_>>(), vec!["GPL-3.0-or-later"]);
+
+        let manifest = root.join("Cargo.toml");
+        let only = root.join("only.rs");
+        let later = root.join("later.rs");
+        let lgpl = root.join("lgpl.rs");
+        let files = [
+            (manifest.as_path(), None),
+            (only.as_path(), Some("GPL-3.0-only".to_string())),
+            (later.as_path(), Some("GPL-3.0-or-later".to_string())),
+        ];
+        assert!(check_packages(files.clone()).is_empty());
+
+        // Other licenses of the same family are still uncovered
+        let files = files.into_iter().chain([(lgpl.as_path(), Some("LGPL-3.0-only".to_string()))]);
+        let results = check_packages(files);
+        assert_eq!(
+            results.first().map(|result| &result.issue),
+            Some(&PackageIssue::HeaderMismatch { uncovered: vec!["LGPL-3.0-only".to_string()] })
+        );
+    }
+
+    #[test]
+    fn skips_workspace_and_private_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["workspace", "monorepo", "app", "crate"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("workspace/Cargo.toml"), "[workspace]\nmembers = [\"crate\"]\n")
+            .unwrap();
+        fs::write(root.join("monorepo/package.json"), r#"{"workspaces": ["packages/*"]}"#).unwrap();
+        fs::write(root.join("app/package.json"), r#"{"name": "app", "private": true}"#).unwrap();
+        fs::write(root.join("crate/Cargo.toml"), "[package]\nname = \"crate\"\n").unwrap();
+
+        let paths: Vec<PathBuf> = [
+            "workspace/Cargo.toml",
+            "monorepo/package.json",
+            "app/package.json",
+            "crate/Cargo.toml",
+        ]
+        .iter()
+        .map(|path| root.join(path))
+        .collect();
+        let results = check_packages(paths.iter().map(|path| (path.as_path(), None)));
+        let manifests: Vec<&Path> =
+            results.iter().map(|result| result.manifest.as_path()).collect();
+        assert_eq!(manifests, vec![root.join("crate/Cargo.toml").as_path()]);
+    }
+}
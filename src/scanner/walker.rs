@@ -2,12 +2,16 @@
 //!
 //! Provides parallel file walking that respects .gitignore and other ignore patterns.
 
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::{DirEntry, WalkBuilder, WalkState};
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 
-use crate::error::ScannerError;
+use crate::config::Config;
+use crate::error::{ConfigError, ScannerError};
+use crate::types::SkipReason;
 
 /// File walker that respects .gitignore and provides parallel iteration
 #[derive(Debug)]
@@ -15,6 +19,13 @@ pub struct FileWalker {
     root: PathBuf,
     additional_ignores: Vec<String>,
     parallel_jobs: usize,
+    includes: Option<Override>,
+    file_types: Option<Override>,
+    hidden: bool,
+    follow_symlinks: bool,
+    max_depth: Option<usize>,
+    gitignore: bool,
+    ignore_filename: Option<String>,
 }
 
 impl FileWalker {
@@ -26,15 +37,89 @@ impl FileWalker {
             root: root.as_ref().to_path_buf(),
             additional_ignores: Vec::new(),
             parallel_jobs: num_cpus::get(),
+            includes: None,
+            file_types: None,
+            hidden: false,
+            follow_symlinks: false,
+            max_depth: None,
+            gitignore: true,
+            ignore_filename: None,
         }
     }
 
+    /// Create a walker with the ignore, include, file type and traversal options
+    /// from the configuration
+    pub fn for_config(root: impl AsRef<Path>, config: &Config) -> crate::error::Result<Self> {
+        let ignore_filename =
+            Some(config.ignore_filename.clone()).filter(|name| !name.trim().is_empty());
+
+        // Reject invalid ignore patterns here; `walk` can only log them
+        build_ignore_matcher(root.as_ref(), &config.ignore_patterns)?;
+
+        Self::new(root)
+            .with_ignores(config.ignore_patterns.clone())
+            .with_hidden(config.include_hidden)
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_max_depth(config.max_depth)
+            .with_gitignore(config.respect_gitignore)
+            .with_ignore_filename(ignore_filename)
+            .with_includes(&config.include_patterns)?
+            .with_file_types(&crate::scanner::filter::file_type_globs(config)?)
+    }
+
     /// Add additional ignore patterns beyond .gitignore
     pub fn with_ignores(mut self, patterns: Vec<String>) -> Self {
         self.additional_ignores = patterns;
         self
     }
 
+    /// Only walk files matching these gitignore-style globs, relative to the root
+    ///
+    /// Patterns starting with `!` exclude matches instead. Ignore rules and hidden
+    /// filtering still apply to included files.
+    pub fn with_includes(mut self, patterns: &[String]) -> crate::error::Result<Self> {
+        self.includes = build_override(&self.root, patterns, "include_patterns")?;
+        Ok(self)
+    }
+
+    /// Only yield files whose name matches one of these globs (e.g. `*.rs`)
+    ///
+    /// Applied on top of the include patterns, so both must match.
+    pub fn with_file_types(mut self, globs: &[String]) -> crate::error::Result<Self> {
+        self.file_types = build_override(&self.root, globs, "only_extensions")?;
+        Ok(self)
+    }
+
+    /// Include hidden files and directories (`.git` itself is always skipped)
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Follow symbolic links; link cycles are detected and skipped
+    pub fn with_follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Limit how deep below the root the walk descends (root children are depth 1)
+    pub fn with_max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Respect .gitignore, the global gitignore and .git/info/exclude
+    pub fn with_gitignore(mut self, gitignore: bool) -> Self {
+        self.gitignore = gitignore;
+        self
+    }
+
+    /// Honour ignore files with this name in every directory (e.g. `.flcignore`)
+    pub fn with_ignore_filename(mut self, name: Option<String>) -> Self {
+        self.ignore_filename = name;
+        self
+    }
+
     /// Set the number of parallel jobs for file walking
     pub fn with_parallelism(mut self, jobs: usize) -> Self {
         self.parallel_jobs = jobs.max(1); // Ensure at least 1 job
@@ -49,19 +134,39 @@ impl FileWalker {
         // Build the walker
         let mut builder = WalkBuilder::new(&self.root);
         builder
-            .hidden(true)           // Skip hidden files and directories
-            .git_ignore(true)       // Respect .gitignore
-            .git_global(true)       // Respect global gitignore
-            .git_exclude(true)      // Respect .git/info/exclude
+            .hidden(!self.hidden)           // Skip hidden files unless requested
+            .git_ignore(self.gitignore)     // Respect .gitignore
+            .git_global(self.gitignore)     // Respect global gitignore
+            .git_exclude(self.gitignore)    // Respect .git/info/exclude
+            .follow_links(self.follow_symlinks)
+            .max_depth(self.max_depth)
             .threads(self.parallel_jobs);
 
-        // Add additional ignore patterns
-        for pattern in &self.additional_ignores {
-            builder.add_ignore(pattern.clone());
+        // Configured ignore patterns use gitignore syntax relative to the root
+        let ignores = match build_ignore_matcher(&self.root, &self.additional_ignores) {
+            Ok(ignores) => ignores,
+            Err(err) => {
+                tracing::warn!("Invalid ignore pattern: {}", err);
+                Gitignore::empty()
+            }
+        };
+
+        // Never descend into the repository database, even with hidden files on
+        builder.filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|file_type| file_type.is_dir());
+            entry.file_name() != ".git" && !ignores.matched(entry.path(), is_dir).is_ignore()
+        });
+
+        if let Some(name) = &self.ignore_filename {
+            builder.add_custom_ignore_filename(name);
         }
 
         // Build and walk in a separate thread to avoid blocking
         let root = self.root.clone();
+        // Includes and file types narrow the walked files after ignore rules and
+        // hidden filtering; as walker overrides their globs would win over both
+        let filters: Vec<Override> =
+            self.includes.iter().chain(&self.file_types).cloned().collect();
         std::thread::spawn(move || {
             builder.build_parallel().run(|| {
                 Box::new(|entry| {
@@ -69,20 +174,35 @@ impl FileWalker {
                         Ok(dir_entry) => {
                             // Only process files
                             if let Some(file_type) = dir_entry.file_type() {
-                                if file_type.is_file() {
+                                let selected = filters.iter().all(|filter| {
+                                    filter.matched(dir_entry.path(), false).is_whitelist()
+                                });
+                                if !selected {
+                                    // Filtered out by include_patterns, only_extensions
+                                    // or only_languages
+                                } else if file_type.is_file() {
                                     let walk_entry =
                                         WalkEntry::from_dir_entry(dir_entry, &root, file_type);
                                     let _ = tx.send(Ok(walk_entry));
+                                } else if file_type.is_symlink() {
+                                    // Links are not followed, but dangling ones are reported
+                                    if let Err(err) = std::fs::metadata(dir_entry.path()) {
+                                        let _ = tx.send(Err(walk_error(
+                                            dir_entry.path().to_path_buf(),
+                                            ignore::Error::Io(err),
+                                        )));
+                                    }
                                 }
                             }
                         }
+                        Err(err) if is_loop(&err) => {
+                            // Symlink cycle: the target was already walked
+                            tracing::debug!("Skipping symlink loop: {}", err);
+                        }
                         Err(err) => {
-                            let _ = tx.send(Err(crate::error::LicenseCheckerError::Scanner(
-                                ScannerError::WalkError {
-                                    path: PathBuf::from("<unknown>"),
-                                    source: err,
-                                },
-                            )));
+                            let path =
+                                error_path(&err).map_or_else(|| root.clone(), Path::to_path_buf);
+                            let _ = tx.send(Err(walk_error(path, err)));
                         }
                     }
                     WalkState::Continue
@@ -96,6 +216,83 @@ impl FileWalker {
     }
 }
 
+/// Build an override matcher from globs, or `None` when there are none
+fn build_override(
+    root: &Path,
+    globs: &[String],
+    field: &'static str,
+) -> crate::error::Result<Option<Override>> {
+    if globs.is_empty() {
+        return Ok(None);
+    }
+
+    let invalid =
+        |err: ignore::Error| ConfigError::InvalidValue { field, message: err.to_string() };
+    let mut builder = OverrideBuilder::new(root);
+    for glob in globs {
+        builder.add(glob).map_err(invalid)?;
+    }
+    Ok(Some(builder.build().map_err(invalid)?))
+}
+
+/// Build a gitignore-style matcher for patterns relative to `dir`
+pub fn build_ignore_matcher(dir: &Path, patterns: &[String]) -> crate::error::Result<Gitignore> {
+    let invalid = |err: ignore::Error| ConfigError::InvalidValue {
+        field: "ignore_patterns",
+        message: err.to_string(),
+    };
+
+    let mut builder = GitignoreBuilder::new(dir);
+    for pattern in patterns {
+        builder.add_line(None, pattern).map_err(invalid)?;
+    }
+    Ok(builder.build().map_err(invalid)?)
+}
+
+/// Check whether an ignore error reports a symlink cycle
+fn is_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_loop(err),
+        _ => false,
+    }
+}
+
+/// Wrap an ignore error in the crate's walk error
+fn walk_error(path: PathBuf, source: ignore::Error) -> crate::error::LicenseCheckerError {
+    crate::error::LicenseCheckerError::Scanner(ScannerError::WalkError { path, source })
+}
+
+/// Extract the path an ignore error refers to, if any
+fn error_path(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        ignore::Error::Partial(errs) => errs.iter().find_map(error_path),
+        _ => None,
+    }
+}
+
+/// Classify a walk error as an access error skip reason
+///
+/// Returns `None` for errors that are not walk errors.
+pub fn access_error_reason(err: &crate::error::LicenseCheckerError) -> Option<SkipReason> {
+    match err {
+        crate::error::LicenseCheckerError::Scanner(ScannerError::WalkError { path, source }) => {
+            Some(match source.io_error() {
+                Some(io_err) => SkipReason::from_io_error(path, io_err),
+                None => SkipReason::IoError { path: path.clone(), error: source.to_string() },
+            })
+        }
+        _ => None,
+    }
+}
+
 /// Entry representing a file found during walking
 #[derive(Debug, Clone)]
 pub struct WalkEntry {
@@ -105,12 +302,22 @@ pub struct WalkEntry {
     pub depth: usize,
     /// File type information
     pub file_type: std::fs::FileType,
+    /// File size in bytes from the walker's metadata, if available
+    pub size: Option<u64>,
 }
 
 impl WalkEntry {
     /// Create a WalkEntry from an ignore::DirEntry
     fn from_dir_entry(entry: DirEntry, _root: &Path, file_type: std::fs::FileType) -> Self {
-        Self { path: entry.path().to_path_buf(), depth: entry.depth(), file_type }
+        let size = entry.metadata().ok().map(|meta| meta.len());
+        Self { path: entry.path().to_path_buf(), depth: entry.depth(), file_type, size }
+    }
+
+    /// Returns the skip reason if the file exceeds `max_file_size`
+    pub fn exceeds_size(&self, max_file_size: Option<u64>) -> Option<SkipReason> {
+        let limit = max_file_size?;
+        let size = self.size?;
+        (size > limit).then_some(SkipReason::TooLarge { size, limit })
     }
 
     /// Get the file extension as a string
@@ -199,4 +406,219 @@ mod tests {
         let entry = entries.into_iter().find(|e| e.path == test_file).unwrap();
         assert_eq!(entry.relative_path(temp_dir.path()).unwrap(), PathBuf::from("src/main.rs"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_reports_broken_symlink_with_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let link = temp_dir.path().join("dangling.rs");
+        std::os::unix::fs::symlink(temp_dir.path().join("missing.rs"), &link).unwrap();
+
+        let walker = FileWalker::new(&temp_dir);
+        let errors: Vec<_> = walker.walk().filter_map(|r| r.err()).collect();
+
+        assert_eq!(errors.len(), 1);
+        let reason = errors.first().and_then(access_error_reason).unwrap();
+        assert!(matches!(reason, SkipReason::BrokenSymlink { ref path, .. } if path == &link));
+    }
+
+    fn walked_names(walker: &FileWalker) -> Vec<String> {
+        let mut names: Vec<String> = walker
+            .walk()
+            .filter_map(|r| r.ok())
+            .filter_map(|e| e.file_name().map(str::to_string))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn walk_with_include_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/nested")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("scripts")).unwrap();
+        fs::write(temp_dir.path().join("src/nested/lib.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("src/notes.txt"), "").unwrap();
+        fs::write(temp_dir.path().join("scripts/build.py"), "").unwrap();
+        fs::write(temp_dir.path().join("build.rs"), "").unwrap();
+
+        let patterns = vec!["src/**/*.rs".to_string(), "scripts/**/*.py".to_string()];
+        let walker = FileWalker::new(&temp_dir).with_includes(&patterns).unwrap();
+
+        assert_eq!(walked_names(&walker), vec!["build.py", "lib.rs"]);
+    }
+
+    #[test]
+    fn walk_with_include_patterns_respects_ignores() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "src/secret.rs\n").unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("src/secret.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("src/.hidden.rs"), "").unwrap();
+
+        let walker =
+            FileWalker::new(&temp_dir).with_includes(&["src/**/*.rs".to_string()]).unwrap();
+        assert_eq!(walked_names(&walker), vec!["main.rs"]);
+    }
+
+    #[test]
+    fn walk_with_file_types_intersects_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("src/app.py"), "").unwrap();
+        fs::write(temp_dir.path().join("build.rs"), "").unwrap();
+
+        let walker = FileWalker::new(&temp_dir)
+            .with_includes(&["src/**".to_string()])
+            .unwrap()
+            .with_file_types(&["*.rs".to_string()])
+            .unwrap();
+
+        assert_eq!(walked_names(&walker), vec!["main.rs"]);
+    }
+
+    #[test]
+    fn walk_hidden_files_only_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".github/workflows")).unwrap();
+        fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".github/workflows/ci.yml"), "").unwrap();
+        fs::write(temp_dir.path().join(".git/config"), "").unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+
+        assert_eq!(walked_names(&FileWalker::new(&temp_dir)), vec!["main.rs"]);
+        assert_eq!(
+            walked_names(&FileWalker::new(&temp_dir).with_hidden(true)),
+            vec!["ci.yml", "main.rs"]
+        );
+    }
+
+    #[test]
+    fn walk_respects_max_depth() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("a/b")).unwrap();
+        fs::write(temp_dir.path().join("top.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("a/mid.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("a/b/deep.rs"), "").unwrap();
+
+        let walker = FileWalker::new(&temp_dir).with_max_depth(Some(2));
+        assert_eq!(walked_names(&walker), vec!["mid.rs", "top.rs"]);
+    }
+
+    #[test]
+    fn walk_honours_custom_ignore_file_at_every_level() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("sub")).unwrap();
+        fs::write(temp_dir.path().join(".flcignore"), "root_skip.rs\n").unwrap();
+        fs::write(temp_dir.path().join("sub/.flcignore"), "*.gen.rs\n").unwrap();
+        fs::write(temp_dir.path().join("root_skip.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("sub/api.gen.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("sub/lib.rs"), "").unwrap();
+
+        let walker = FileWalker::new(&temp_dir).with_ignore_filename(Some(".flcignore".into()));
+        assert_eq!(walked_names(&walker), vec!["lib.rs"]);
+    }
+
+    #[test]
+    fn walk_without_gitignore() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "ignored.rs\n").unwrap();
+        fs::write(temp_dir.path().join("ignored.rs"), "").unwrap();
+
+        assert!(walked_names(&FileWalker::new(&temp_dir)).is_empty());
+        let walker = FileWalker::new(&temp_dir).with_gitignore(false);
+        assert_eq!(walked_names(&walker), vec!["ignored.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_follows_symlinks_without_looping() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("real")).unwrap();
+        fs::write(temp_dir.path().join("real/lib.rs"), "").unwrap();
+        std::os::unix::fs::symlink(temp_dir.path(), temp_dir.path().join("real/cycle")).unwrap();
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("real/lib.rs"),
+            temp_dir.path().join("link.rs"),
+        )
+        .unwrap();
+
+        assert_eq!(walked_names(&FileWalker::new(&temp_dir)), vec!["lib.rs"]);
+
+        let walker = FileWalker::new(&temp_dir).with_follow_symlinks(true);
+        let results: Vec<_> = walker.walk().collect();
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(walked_names(&walker), vec!["lib.rs", "link.rs"]);
+    }
+
+    #[test]
+    fn walk_applies_ignore_patterns() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("target/debug")).unwrap();
+        fs::write(temp_dir.path().join("target/debug/out.rs"), "").unwrap();
+        fs::write(temp_dir.path().join("scratch.tmp"), "").unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+
+        let walker = FileWalker::new(&temp_dir)
+            .with_ignores(vec!["*.tmp".to_string(), "target/".to_string()]);
+        assert_eq!(walked_names(&walker), vec!["main.rs"]);
+    }
+
+    #[test]
+    fn walk_with_invalid_include_pattern() {
+        let result = FileWalker::new("/tmp").with_includes(&["src/[".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn for_config_rejects_invalid_ignore_pattern() {
+        let mut config = Config::default();
+        config.ignore_patterns.push("src/{a".to_string());
+
+        let result = FileWalker::for_config("/tmp", &config);
+        assert!(matches!(
+            result,
+            Err(crate::error::LicenseCheckerError::Config(ConfigError::InvalidValue {
+                field: "ignore_patterns",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn walk_entry_records_size() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("big.js"), "x".repeat(100)).unwrap();
+
+        let walker = FileWalker::new(&temp_dir);
+        let entry = walker.walk().filter_map(|r| r.ok()).find(|e| e.is_file()).unwrap();
+
+        assert_eq!(entry.size, Some(100));
+        assert_eq!(entry.exceeds_size(None), None);
+        assert_eq!(entry.exceeds_size(Some(100)), None);
+        assert_eq!(
+            entry.exceeds_size(Some(99)),
+            Some(SkipReason::TooLarge { size: 100, limit: 99 })
+        );
+    }
+
+    #[test]
+    fn error_path_unwraps_nested_errors() {
+        let inner = ignore::Error::Io(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        let err = ignore::Error::WithDepth {
+            depth: 1,
+            err: Box::new(ignore::Error::WithPath {
+                path: PathBuf::from("locked"),
+                err: Box::new(inner),
+            }),
+        };
+
+        assert_eq!(error_path(&err), Some(Path::new("locked")));
+        let reason = access_error_reason(&walk_error(PathBuf::from("locked"), err)).unwrap();
+        assert!(matches!(reason, SkipReason::PermissionDenied { .. }));
+    }
 }
@@ -181,6 +181,66 @@ impl std::fmt::Display for MaxHeaderBytes {
     }
 }
 
+/// Line terminator convention of a text file.
+///
+/// Headers are rendered with `\n` and converted to the file's dominant
+/// convention so fixing a CRLF file does not produce mixed line endings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LineEnding {
+    /// Unix-style `\n`
+    #[default]
+    Lf,
+    /// Windows-style `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// Detects the dominant line ending (ties and files without newlines are `Lf`).
+    pub fn detect(content: &[u8]) -> Self {
+        let mut crlf = 0usize;
+        let mut lf = 0usize;
+        for pos in memchr::memchr_iter(b'\n', content) {
+            let is_crlf = pos.checked_sub(1).and_then(|prev| content.get(prev)) == Some(&b'\r');
+            if is_crlf {
+                crlf = crlf.saturating_add(1);
+            } else {
+                lf = lf.saturating_add(1);
+            }
+        }
+
+        if crlf > lf {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+
+    /// Returns the line terminator as a string slice.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// Converts `\n`-terminated text to this line ending.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            Self::Lf => text.to_string(),
+            Self::CrLf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+impl std::fmt::Display for LineEnding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lf => write!(f, "LF"),
+            Self::CrLf => write!(f, "CRLF"),
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -425,4 +485,23 @@ mod tests {
         let value: usize = mhb.into();
         assert_eq!(value, 8192);
     }
+
+    // LineEnding tests
+    #[test]
+    fn line_ending_detect() {
+        assert_eq!(LineEnding::detect(b"a\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect(b"a\r\nb\r\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect(b"a\r\nb\r\nc\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect(b"a\r\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect(b"no newline"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect(b"\n"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn line_ending_apply() {
+        assert_eq!(LineEnding::Lf.apply("a\nb\n"), "a\nb\n");
+        assert_eq!(LineEnding::CrLf.apply("a\nb\n"), "a\r\nb\r\n");
+        assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+        assert_eq!(LineEnding::CrLf.to_string(), "CRLF");
+    }
 }
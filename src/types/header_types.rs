@@ -55,18 +55,46 @@ impl std::fmt::Display for LicenseHeader {
 ///
 /// Defines how to format license headers for different programming languages.
 /// Supports both line comments (e.g., `//`, `#`) and block comments (e.g., `/* */`).
+///
+/// A style may also describe a full block layout with an opening line, a per-line
+/// decoration and a closing line, e.g. Javadoc-style `/*`, ` *`, ` */` or a banner
+/// of `////` lines. When any of these parts is set the header is rendered with the
+/// layout, both when checking and when fixing.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CommentStyle {
     /// The prefix for comment lines (e.g., "//", "#", "/*")
     pub prefix: String,
     /// Optional suffix for block comments (e.g., "*/")
     pub suffix: Option<String>,
+    /// Optional line emitted before the header (e.g., "/*", "/**", "////")
+    #[serde(default)]
+    pub opening: Option<String>,
+    /// Optional decoration placed before each header line (e.g., " *")
+    #[serde(default)]
+    pub line_prefix: Option<String>,
+    /// Optional line emitted after the header (e.g., " */", "////")
+    #[serde(default)]
+    pub closing: Option<String>,
+    /// Whether a blank line separates the header from the rest of the file
+    #[serde(default = "default_blank_line_after")]
+    pub blank_line_after: bool,
+}
+
+fn default_blank_line_after() -> bool {
+    true
 }
 
 impl CommentStyle {
     /// Creates a new comment style.
     pub fn new(prefix: String, suffix: Option<String>) -> Self {
-        Self { prefix, suffix }
+        Self {
+            prefix,
+            suffix,
+            opening: None,
+            line_prefix: None,
+            closing: None,
+            blank_line_after: true,
+        }
     }
 
     /// Returns a line comment style (e.g., "//" for Rust, "#" for Python).
@@ -79,6 +107,27 @@ impl CommentStyle {
         Self::new(prefix.to_string(), Some(suffix.to_string()))
     }
 
+    /// Returns a layout style with opening, per-line and closing parts.
+    pub fn layout(opening: &str, line_prefix: &str, closing: &str) -> Self {
+        Self {
+            opening: Some(opening.to_string()),
+            line_prefix: Some(line_prefix.to_string()),
+            closing: Some(closing.to_string()),
+            ..Self::new(line_prefix.to_string(), None)
+        }
+    }
+
+    /// Javadoc-style block comments (`/*`, ` *`, ` */`).
+    pub fn javadoc_style() -> Self {
+        Self::layout("/*", " *", " */")
+    }
+
+    /// Sets whether a blank line follows the header.
+    pub fn with_blank_line_after(mut self, blank_line_after: bool) -> Self {
+        self.blank_line_after = blank_line_after;
+        self
+    }
+
     /// Rust-style line comments (`//`).
     pub fn rust_style() -> Self {
         Self::line_comment("//")
@@ -134,6 +183,26 @@ impl CommentStyle {
         self.suffix.is_some()
     }
 
+    /// Returns true if an opening, per-line or closing part is configured.
+    pub fn has_layout(&self) -> bool {
+        self.opening.is_some() || self.line_prefix.is_some() || self.closing.is_some()
+    }
+
+    /// Trimmed opening and closing delimiters of a block comment, if any.
+    ///
+    /// Layout styles use their opening and closing lines; block styles use
+    /// the prefix and suffix.
+    pub fn block_delimiters(&self) -> Option<(&str, &str)> {
+        let block = if self.has_layout() {
+            self.opening.as_deref().zip(self.closing.as_deref())
+        } else {
+            self.suffix.as_deref().map(|suffix| (self.prefix.as_str(), suffix))
+        };
+        block
+            .map(|(open, close)| (open.trim(), close.trim()))
+            .filter(|(open, close)| !open.is_empty() && !close.is_empty())
+    }
+
     /// Formats a license header line according to this style.
     ///
     /// For line comments: `prefix header_line`
@@ -144,6 +213,38 @@ impl CommentStyle {
             None => format!("{}{}", self.prefix, header_line),
         }
     }
+
+    /// Renders a header with the configured layout, one `\n`-terminated line each.
+    ///
+    /// Each header line is written as `line_prefix line` (falling back to `prefix`),
+    /// empty header lines keep only the decoration, and the opening and closing
+    /// parts are emitted on their own lines when set. The trailing blank line is
+    /// not included; callers decide based on `blank_line_after`.
+    pub fn render_layout(&self, header: &LicenseHeader) -> String {
+        let decoration = self.line_prefix.as_deref().unwrap_or(&self.prefix);
+        let mut result = String::new();
+
+        if let Some(opening) = &self.opening {
+            result.push_str(opening);
+            result.push('\n');
+        }
+
+        for line in header.as_str().lines() {
+            result.push_str(decoration);
+            if !line.is_empty() {
+                result.push(' ');
+                result.push_str(line);
+            }
+            result.push('\n');
+        }
+
+        if let Some(closing) = &self.closing {
+            result.push_str(closing);
+            result.push('\n');
+        }
+
+        result
+    }
 }
 
 impl Default for CommentStyle {
@@ -279,6 +380,18 @@ mod tests {
         assert_eq!(CommentStyle::css_style(), CommentStyle::block_comment("/*", "*/"));
     }
 
+    #[test]
+    fn comment_style_layout_render() {
+        let header = LicenseHeader::new("MIT License\n\nCopyright 2024").unwrap();
+        let style = CommentStyle::javadoc_style();
+        assert!(style.has_layout());
+        assert!(!CommentStyle::rust_style().has_layout());
+        assert_eq!(
+            style.render_layout(&header),
+            "/*\n * MIT License\n *\n * Copyright 2024\n */\n"
+        );
+    }
+
     #[test]
     fn similarity_score_new() {
         let score = SimilarityScore::new(85);
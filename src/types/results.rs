@@ -3,6 +3,7 @@
 //! Types that represent the outcomes of scanning and fixing operations.
 
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use super::{FilePath, SimilarityScore};
@@ -24,9 +25,37 @@ pub enum FileStatus {
         /// Why the file was skipped.
         reason: SkipReason,
     },
+    /// File could not be read and unreadable files are treated as failures.
+    Unreadable {
+        /// The access error (permission denied, I/O error or broken symlink).
+        reason: SkipReason,
+    },
+    /// File carries a recognised license other than the expected one.
+    ForeignLicense {
+        /// SPDX identifier or expression of the license found.
+        detected: String,
+    },
+    /// Third-party file matched by a `[[third_party]]` rule.
+    ThirdParty {
+        /// The license found in the file's header.
+        license: LicenseClassification,
+        /// Whether the rule accepts this license.
+        allowed: bool,
+    },
 }
 
 impl FileStatus {
+    /// Creates the status for a file that could not be read.
+    ///
+    /// Returns `Unreadable` when `fail_on_unreadable` is set, otherwise `Skipped`.
+    pub fn unreadable(reason: SkipReason, fail_on_unreadable: bool) -> Self {
+        if fail_on_unreadable {
+            FileStatus::Unreadable { reason }
+        } else {
+            FileStatus::Skipped { reason }
+        }
+    }
+
     /// Returns true if the file has a valid header.
     pub fn has_valid_header(&self) -> bool {
         matches!(self, FileStatus::HasHeader)
@@ -47,6 +76,50 @@ impl FileStatus {
         matches!(self, FileStatus::Skipped { .. })
     }
 
+    /// Returns true if the file could not be read and counts as a failure.
+    pub fn is_unreadable(&self) -> bool {
+        matches!(self, FileStatus::Unreadable { .. })
+    }
+
+    /// Returns true if the file carries a different, recognised license.
+    pub fn is_foreign_license(&self) -> bool {
+        matches!(self, FileStatus::ForeignLicense { .. })
+    }
+
+    /// Returns true if the file is third-party code matched by a `[[third_party]]` rule.
+    pub fn is_third_party(&self) -> bool {
+        matches!(self, FileStatus::ThirdParty { .. })
+    }
+
+    /// Returns true if the file passes the check: our header or an allowed third-party license.
+    pub fn is_passed(&self) -> bool {
+        matches!(self, FileStatus::HasHeader | FileStatus::ThirdParty { allowed: true, .. })
+    }
+
+    /// Returns true if the file fails the check.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            FileStatus::MissingHeader
+                | FileStatus::MalformedHeader { .. }
+                | FileStatus::Unreadable { .. }
+                | FileStatus::ForeignLicense { .. }
+                | FileStatus::ThirdParty { allowed: false, .. }
+        )
+    }
+
+    /// Returns the access error if the file could not be read, whether skipped or failed.
+    pub fn access_error(&self) -> Option<&SkipReason> {
+        match self {
+            FileStatus::Skipped { reason } | FileStatus::Unreadable { reason }
+                if reason.is_access_error() =>
+            {
+                Some(reason)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the similarity score if this is a malformed header.
     pub fn similarity_score(&self) -> Option<SimilarityScore> {
         match self {
@@ -62,6 +135,11 @@ impl FileStatus {
             _ => None,
         }
     }
+
+    /// Returns true if the file was skipped by an in-file suppression marker.
+    pub fn is_suppressed(&self) -> bool {
+        matches!(self.skip_reason(), Some(SkipReason::Suppressed { .. }))
+    }
 }
 
 impl std::fmt::Display for FileStatus {
@@ -73,10 +151,211 @@ impl std::fmt::Display for FileStatus {
                 write!(f, "malformed header ({} similar)", similarity)
             }
             FileStatus::Skipped { reason } => write!(f, "skipped ({})", reason),
+            FileStatus::Unreadable { reason } => write!(f, "unreadable ({})", reason),
+            FileStatus::ForeignLicense { detected } => write!(f, "foreign license ({})", detected),
+            FileStatus::ThirdParty { license, allowed: true } => {
+                write!(f, "third-party ({})", license)
+            }
+            FileStatus::ThirdParty { license, allowed: false } => {
+                write!(f, "third-party ({}, not allowed)", license)
+            }
         }
     }
 }
 
+/// The license a file's header was classified as.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LicenseClassification {
+    /// The configured license header.
+    Ours,
+    /// A license recognised by SPDX identifier, bundled text or name.
+    Known {
+        /// SPDX identifier or expression, e.g. `MIT` or `MIT OR Apache-2.0`.
+        id: String,
+    },
+    /// License text that matches no known license.
+    Unknown,
+    /// No license text at all.
+    None,
+}
+
+impl std::fmt::Display for LicenseClassification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseClassification::Ours => write!(f, "our header"),
+            LicenseClassification::Known { id } => write!(f, "known license {}", id),
+            LicenseClassification::Unknown => write!(f, "unknown license"),
+            LicenseClassification::None => write!(f, "no license"),
+        }
+    }
+}
+
+/// The license policy's decision for a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyVerdict {
+    /// The license is allowed.
+    Allowed,
+    /// The license is allowed but must be reviewed.
+    Review,
+    /// The license is denied and fails the check.
+    Denied,
+}
+
+impl std::fmt::Display for PolicyVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyVerdict::Allowed => write!(f, "allowed"),
+            PolicyVerdict::Review => write!(f, "review"),
+            PolicyVerdict::Denied => write!(f, "denied"),
+        }
+    }
+}
+
+/// The license policy's decision for a single file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PolicyResult {
+    /// The file the license was found in.
+    pub path: FilePath,
+    /// SPDX identifier or expression of the file's license.
+    pub license: String,
+    /// The policy's decision.
+    pub verdict: PolicyVerdict,
+}
+
+impl std::fmt::Display for PolicyResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.path, self.license, self.verdict)
+    }
+}
+
+/// A problem with the copyright holder named in a file's header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum CopyrightIssue {
+    /// The holder is not among the allowed holders.
+    UnknownHolder,
+    /// The holder looks like an individual's name and is not among the allowed holders.
+    IndividualHolder,
+    /// The holder is allowed but spelled differently from its canonical form.
+    NonCanonical {
+        /// The configured spelling.
+        canonical: String,
+    },
+    /// The statement's years do not cover the file's first and last commit.
+    OutdatedYears {
+        /// The year range that covers the git history, e.g. `2019-2026`.
+        expected: String,
+    },
+    /// Commit authors are not named by any statement or covered by an organisation.
+    UncoveredAuthors {
+        /// The authors missing from the statements.
+        authors: Vec<String>,
+    },
+}
+
+impl CopyrightIssue {
+    /// Returns true if the issue fails the check (spelling variants only warn).
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, CopyrightIssue::NonCanonical { .. })
+    }
+
+    /// Returns true if the fixer can resolve the issue.
+    pub fn is_fixable(&self) -> bool {
+        matches!(self, CopyrightIssue::NonCanonical { .. } | CopyrightIssue::OutdatedYears { .. })
+    }
+}
+
+impl std::fmt::Display for CopyrightIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyrightIssue::UnknownHolder => write!(f, "unknown holder"),
+            CopyrightIssue::IndividualHolder => write!(f, "individual holder"),
+            CopyrightIssue::NonCanonical { canonical } => write!(f, "should be {}", canonical),
+            CopyrightIssue::OutdatedYears { expected } => {
+                write!(f, "years should be {}", expected)
+            }
+            CopyrightIssue::UncoveredAuthors { authors } => {
+                write!(f, "does not cover authors {}", authors.join(", "))
+            }
+        }
+    }
+}
+
+/// A copyright holder flagged in a file's header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CopyrightResult {
+    /// The file the statement was found in.
+    pub path: FilePath,
+    /// The holder as written in the file.
+    pub holder: String,
+    /// What is wrong with it.
+    pub issue: CopyrightIssue,
+}
+
+impl std::fmt::Display for CopyrightResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.path, self.holder, self.issue)
+    }
+}
+
+/// A problem with a package's license file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum PackageIssue {
+    /// The package directory has no LICENSE or COPYING file.
+    MissingLicenseFile,
+    /// No license file in the package matches a known license.
+    UnrecognisedLicenseFile,
+    /// File headers carry licenses the license files do not cover.
+    HeaderMismatch {
+        /// License identifiers found in headers but not in the license files.
+        uncovered: Vec<String>,
+    },
+}
+
+impl std::fmt::Display for PackageIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackageIssue::MissingLicenseFile => write!(f, "no LICENSE or COPYING file"),
+            PackageIssue::UnrecognisedLicenseFile => {
+                write!(f, "license file matches no known license")
+            }
+            PackageIssue::HeaderMismatch { uncovered } => {
+                write!(f, "headers use {} but the license files do not", uncovered.join(", "))
+            }
+        }
+    }
+}
+
+/// A package whose license file is missing or does not match its headers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PackageResult {
+    /// The package manifest; its directory is the package root.
+    pub manifest: FilePath,
+    /// Licenses recognised in the package's license files.
+    pub licenses: Vec<String>,
+    /// What is wrong with it.
+    pub issue: PackageIssue,
+}
+
+impl std::fmt::Display for PackageResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.manifest, self.issue)
+    }
+}
+
+/// Checksum and license evidence for a file read during a scan.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FileEvidence {
+    /// The file that was read.
+    pub path: FilePath,
+    /// SPDX identifier or expression of the license found in its header, if recognised.
+    pub license: Option<String>,
+    /// Lowercase hex SHA-256 of the whole file.
+    pub sha256: String,
+}
+
 /// Reasons why a file might be skipped during scanning.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SkipReason {
@@ -86,12 +365,84 @@ pub enum SkipReason {
     Empty,
     /// File is ignored by .gitignore rules.
     Gitignored,
-    /// File is too large to check efficiently.
-    TooLarge,
+    /// File is larger than the configured `max_file_size`.
+    TooLarge {
+        /// File size in bytes.
+        size: u64,
+        /// The configured limit in bytes.
+        limit: u64,
+    },
     /// File encoding is not supported.
     UnsupportedEncoding,
     /// No comment style configured for this file type.
     NoCommentStyle,
+    /// File or directory could not be opened due to missing permissions.
+    PermissionDenied {
+        /// The path that could not be accessed.
+        path: PathBuf,
+        /// The OS error message.
+        error: String,
+    },
+    /// File or directory could not be read due to an I/O error.
+    IoError {
+        /// The path that could not be read.
+        path: PathBuf,
+        /// The OS error message.
+        error: String,
+    },
+    /// Symbolic link whose target does not exist.
+    BrokenSymlink {
+        /// The path of the link itself.
+        path: PathBuf,
+        /// The OS error message from resolving the target.
+        error: String,
+    },
+    /// File opts out of header checks with an in-file marker such as `flc:ignore-file`.
+    Suppressed {
+        /// The marker found in the file.
+        marker: String,
+        /// Justification written after the marker, if any.
+        justification: Option<String>,
+    },
+}
+
+impl SkipReason {
+    /// Classifies an I/O error raised while accessing `path`.
+    pub fn from_io_error(path: &Path, error: &std::io::Error) -> Self {
+        let path_buf = path.to_path_buf();
+        let error_text = error.to_string();
+
+        if error.kind() == std::io::ErrorKind::PermissionDenied {
+            return SkipReason::PermissionDenied { path: path_buf, error: error_text };
+        }
+
+        let is_symlink = path.symlink_metadata().is_ok_and(|meta| meta.file_type().is_symlink());
+        if is_symlink && path.metadata().is_err() {
+            return SkipReason::BrokenSymlink { path: path_buf, error: error_text };
+        }
+
+        SkipReason::IoError { path: path_buf, error: error_text }
+    }
+
+    /// Returns true if the file was skipped because it could not be accessed.
+    pub fn is_access_error(&self) -> bool {
+        matches!(
+            self,
+            SkipReason::PermissionDenied { .. }
+                | SkipReason::IoError { .. }
+                | SkipReason::BrokenSymlink { .. }
+        )
+    }
+
+    /// Returns the path and OS error message for access errors.
+    pub fn access_error(&self) -> Option<(&Path, &str)> {
+        match self {
+            SkipReason::PermissionDenied { path, error }
+            | SkipReason::IoError { path, error }
+            | SkipReason::BrokenSymlink { path, error } => Some((path, error)),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for SkipReason {
@@ -100,9 +451,22 @@ impl std::fmt::Display for SkipReason {
             SkipReason::Binary => write!(f, "binary file"),
             SkipReason::Empty => write!(f, "empty file"),
             SkipReason::Gitignored => write!(f, "gitignored"),
-            SkipReason::TooLarge => write!(f, "too large"),
+            SkipReason::TooLarge { size, limit } => {
+                write!(f, "too large ({} bytes > {} bytes)", size, limit)
+            }
             SkipReason::UnsupportedEncoding => write!(f, "unsupported encoding"),
             SkipReason::NoCommentStyle => write!(f, "no comment style"),
+            SkipReason::PermissionDenied { error, .. } => {
+                write!(f, "permission denied: {}", error)
+            }
+            SkipReason::IoError { error, .. } => write!(f, "I/O error: {}", error),
+            SkipReason::BrokenSymlink { error, .. } => write!(f, "broken symlink: {}", error),
+            SkipReason::Suppressed { marker, justification: Some(justification) } => {
+                write!(f, "suppressed by {}: {}", marker, justification)
+            }
+            SkipReason::Suppressed { marker, justification: None } => {
+                write!(f, "suppressed by {}", marker)
+            }
         }
     }
 }
@@ -211,9 +575,10 @@ impl ScanResult {
         self.status.has_valid_header()
     }
 
-    /// Returns true if this result requires attention (missing or malformed header).
+    /// Returns true if this result requires attention (missing or malformed header,
+    /// or unreadable when treated as a failure).
     pub fn needs_attention(&self) -> bool {
-        self.status.is_missing_header() || self.status.is_malformed_header()
+        self.status.is_failure()
     }
 }
 
@@ -261,6 +626,39 @@ pub struct ScanSummary {
     pub failed: usize,
     /// Number of files that were skipped.
     pub skipped: usize,
+    /// Number of files a fix was attempted on but could not be written (fix mode only).
+    #[serde(default)]
+    pub fix_errors: usize,
+    /// Number of files skipped for exceeding `max_file_size` (also counted in `skipped`).
+    #[serde(default)]
+    pub too_large: usize,
+    /// Files and directories that could not be read, with their access errors.
+    #[serde(default)]
+    pub unreadable: Vec<ScanResult>,
+    /// Files skipped by an in-file suppression marker (also counted in `skipped`).
+    #[serde(default)]
+    pub suppressed: Vec<ScanResult>,
+    /// Files matched by `[[third_party]]` rules (also counted in `passed` or `failed`).
+    #[serde(default)]
+    pub third_party: Vec<ScanResult>,
+    /// Files carrying a different, recognised license (also counted in `failed`).
+    #[serde(default)]
+    pub foreign_licenses: Vec<ScanResult>,
+    /// License policy decisions for files with a recognised license (denied files
+    /// are counted in `failed`).
+    #[serde(default)]
+    pub policy: Vec<PolicyResult>,
+    /// Copyright holders flagged by `[copyright]` (files with unknown or individual
+    /// holders are counted in `failed`).
+    #[serde(default)]
+    pub copyright: Vec<CopyrightResult>,
+    /// Packages whose license file is missing, unrecognised or does not cover their
+    /// headers, when package checks are enabled.
+    #[serde(default)]
+    pub packages: Vec<PackageResult>,
+    /// Checksums and license evidence, collected when the scanner is asked for them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<FileEvidence>,
     /// How long the scan took.
     pub duration: Duration,
 }
@@ -274,7 +672,92 @@ impl ScanSummary {
         skipped: usize,
         duration: Duration,
     ) -> Self {
-        Self { total, passed, failed, skipped, duration }
+        Self {
+            total,
+            passed,
+            failed,
+            skipped,
+            fix_errors: 0,
+            too_large: 0,
+            unreadable: Vec::new(),
+            suppressed: Vec::new(),
+            third_party: Vec::new(),
+            foreign_licenses: Vec::new(),
+            policy: Vec::new(),
+            copyright: Vec::new(),
+            packages: Vec::new(),
+            evidence: Vec::new(),
+            duration,
+        }
+    }
+
+    /// Records how many attempted fixes failed (these are also counted in `failed`).
+    pub fn with_fix_errors(mut self, fix_errors: usize) -> Self {
+        self.fix_errors = fix_errors;
+        self
+    }
+
+    /// Records how many files were skipped for exceeding `max_file_size`.
+    pub fn with_too_large(mut self, too_large: usize) -> Self {
+        self.too_large = too_large;
+        self
+    }
+
+    /// Records the files that could not be read (also counted in `skipped` or `failed`),
+    /// in path order.
+    pub fn with_unreadable(mut self, mut unreadable: Vec<ScanResult>) -> Self {
+        unreadable.sort_by(|a, b| a.path.cmp(&b.path));
+        self.unreadable = unreadable;
+        self
+    }
+
+    /// Records the files skipped by suppression markers, in path order.
+    pub fn with_suppressed(mut self, mut suppressed: Vec<ScanResult>) -> Self {
+        suppressed.sort_by(|a, b| a.path.cmp(&b.path));
+        self.suppressed = suppressed;
+        self
+    }
+
+    /// Records the files matched by `[[third_party]]` rules, in path order.
+    pub fn with_third_party(mut self, mut third_party: Vec<ScanResult>) -> Self {
+        third_party.sort_by(|a, b| a.path.cmp(&b.path));
+        self.third_party = third_party;
+        self
+    }
+
+    /// Records the files carrying a different, recognised license, in path order.
+    pub fn with_foreign_licenses(mut self, mut foreign_licenses: Vec<ScanResult>) -> Self {
+        foreign_licenses.sort_by(|a, b| a.path.cmp(&b.path));
+        self.foreign_licenses = foreign_licenses;
+        self
+    }
+
+    /// Records the license policy decisions, in path order.
+    pub fn with_policy(mut self, mut policy: Vec<PolicyResult>) -> Self {
+        policy.sort_by(|a, b| a.path.cmp(&b.path));
+        self.policy = policy;
+        self
+    }
+
+    /// Records the flagged copyright holders, in path order; a file's own stay in
+    /// header order.
+    pub fn with_copyright(mut self, mut copyright: Vec<CopyrightResult>) -> Self {
+        copyright.sort_by(|a, b| a.path.cmp(&b.path));
+        self.copyright = copyright;
+        self
+    }
+
+    /// Records the packages with license file problems.
+    pub fn with_packages(mut self, packages: Vec<PackageResult>) -> Self {
+        self.packages = packages;
+        self
+    }
+
+    /// Records the per-file checksums and license evidence, in path order.
+    pub fn with_evidence(mut self, mut evidence: Vec<FileEvidence>) -> Self {
+        evidence.sort_by(|a, b| a.path.cmp(&b.path));
+        self.evidence = evidence;
+        self
     }
 
     /// Returns the number of files that need attention (failed + skipped).
@@ -378,6 +861,33 @@ mod tests {
         assert!(!dirty.is_clean());
     }
 
+    #[test]
+    fn scan_summary_sorts_file_lists_by_path() {
+        let result =
+            |path: &str, status: FileStatus| ScanResult::new(FilePath::new(path.into()), status);
+        let foreign = || FileStatus::ForeignLicense { detected: "GPL-2.0-only".to_string() };
+        let policy = |path: &str| PolicyResult {
+            path: FilePath::new(path.into()),
+            license: "MIT".to_string(),
+            verdict: PolicyVerdict::Allowed,
+        };
+        let summary = ScanSummary::default()
+            .with_foreign_licenses(vec![result("src/b.rs", foreign()), result("a.rs", foreign())])
+            .with_policy(vec![policy("z.rs"), policy("m/a.rs"), policy("b.rs")]);
+
+        let paths = |paths: Vec<&FilePath>| -> Vec<String> {
+            paths.iter().map(|path| path.to_string()).collect()
+        };
+        assert_eq!(
+            paths(summary.foreign_licenses.iter().map(|r| &r.path).collect()),
+            vec!["a.rs", "src/b.rs"]
+        );
+        assert_eq!(
+            paths(summary.policy.iter().map(|r| &r.path).collect()),
+            vec!["b.rs", "m/a.rs", "z.rs"]
+        );
+    }
+
     // FileStatus additional tests
     #[test]
     fn file_status_is_missing_header() {
@@ -430,7 +940,10 @@ mod tests {
         assert_eq!(SkipReason::Binary.to_string(), "binary file");
         assert_eq!(SkipReason::Empty.to_string(), "empty file");
         assert_eq!(SkipReason::Gitignored.to_string(), "gitignored");
-        assert_eq!(SkipReason::TooLarge.to_string(), "too large");
+        assert_eq!(
+            SkipReason::TooLarge { size: 2048, limit: 1024 }.to_string(),
+            "too large (2048 bytes > 1024 bytes)"
+        );
         assert_eq!(SkipReason::UnsupportedEncoding.to_string(), "unsupported encoding");
         assert_eq!(SkipReason::NoCommentStyle.to_string(), "no comment style");
     }
@@ -565,4 +1078,65 @@ mod tests {
         assert_eq!(default.skipped, 0);
         assert_eq!(default.duration, Duration::default());
     }
+
+    #[test]
+    fn skip_reason_from_io_error() {
+        let path = Path::new("/nonexistent/secret.rs");
+
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let reason = SkipReason::from_io_error(path, &denied);
+        assert!(matches!(reason, SkipReason::PermissionDenied { .. }));
+        assert!(reason.is_access_error());
+        assert_eq!(reason.access_error().unwrap().0, path);
+
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let reason = SkipReason::from_io_error(path, &missing);
+        assert!(matches!(reason, SkipReason::IoError { .. }));
+        assert!(reason.to_string().starts_with("I/O error: "));
+
+        assert!(!SkipReason::Binary.is_access_error());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn skip_reason_detects_broken_symlink() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let link = temp_dir.path().join("dangling.rs");
+        std::os::unix::fs::symlink(temp_dir.path().join("missing.rs"), &link).unwrap();
+
+        let error = std::fs::metadata(&link).unwrap_err();
+        let reason = SkipReason::from_io_error(&link, &error);
+        assert!(matches!(reason, SkipReason::BrokenSymlink { ref path, .. } if path == &link));
+    }
+
+    #[test]
+    fn skip_reason_suppressed_display() {
+        let reason = SkipReason::Suppressed {
+            marker: "flc:ignore-file".to_string(),
+            justification: Some("generated by protoc".to_string()),
+        };
+        assert_eq!(reason.to_string(), "suppressed by flc:ignore-file: generated by protoc");
+        assert!(FileStatus::Skipped { reason }.is_suppressed());
+
+        let reason =
+            SkipReason::Suppressed { marker: "flc:disable".to_string(), justification: None };
+        assert_eq!(reason.to_string(), "suppressed by flc:disable");
+    }
+
+    #[test]
+    fn file_status_unreadable() {
+        let reason = SkipReason::IoError { path: PathBuf::from("a.rs"), error: "boom".into() };
+
+        let skipped = FileStatus::unreadable(reason.clone(), false);
+        assert!(skipped.is_skipped());
+        assert_eq!(skipped.access_error(), Some(&reason));
+
+        let failed = FileStatus::unreadable(reason.clone(), true);
+        assert!(failed.is_unreadable());
+        assert_eq!(failed.access_error(), Some(&reason));
+        assert_eq!(failed.to_string(), "unreadable (I/O error: boom)");
+        assert!(ScanResult::new(FilePath::new("a.rs".into()), failed).needs_attention());
+
+        assert_eq!(FileStatus::Skipped { reason: SkipReason::Binary }.access_error(), None);
+    }
 }
@@ -102,15 +102,15 @@ pub fn create_test_config() -> fast_license_checker::config::Config {
 
     config
         .comment_styles
-        .insert("rs".to_string(), CommentStyleConfig { prefix: "//".to_string(), suffix: None });
+        .insert("rs".to_string(), CommentStyleConfig::line_comment("//"));
 
     config
         .comment_styles
-        .insert("py".to_string(), CommentStyleConfig { prefix: "#".to_string(), suffix: None });
+        .insert("py".to_string(), CommentStyleConfig::line_comment("#"));
 
     config.comment_styles.insert(
         "xml".to_string(),
-        CommentStyleConfig { prefix: "<!--".to_string(), suffix: Some("-->".to_string()) },
+        CommentStyleConfig::block_comment("<!--", "-->"),
     );
 
     config